@@ -1,6 +1,7 @@
 //add bechmark for binary field multiplication
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use linearalgebra::binary::Newable;
+use linearalgebra::binary_table::LogTableField;
 use linearalgebra::{Field, Ring};
 
 //write same benchmarks as above for u8
@@ -9,12 +10,13 @@ pub fn bench_binary_field_mul_u8(c: &mut Criterion) {
     let field = linearalgebra::binary::BinaryField::<u8>::new();
     group.bench_function("binary_field_mul_u8", |b| {
         b.iter(|| {
-            let a = 0x8f;
-            let b = 0x33;
+            let a = black_box(0x8f);
+            let b = black_box(0x33);
             let c = field.mul(&a, &b);
             black_box(c);
         })
     });
+    group.finish();
 }
 
 pub fn bench_binary_field_inv_u8(c: &mut Criterion) {
@@ -22,11 +24,12 @@ pub fn bench_binary_field_inv_u8(c: &mut Criterion) {
     let field = linearalgebra::binary::BinaryField::<u8>::new();
     group.bench_function("binary_field_inv_u8", |b| {
         b.iter(|| {
-            let a = 0x8f;
+            let a = black_box(0x8f);
             let b = field.inv(&a).unwrap();
             black_box(b);
         })
     });
+    group.finish();
 }
 
 pub fn bench_binary_field_inv_gcd_u8(c: &mut Criterion) {
@@ -39,6 +42,7 @@ pub fn bench_binary_field_inv_gcd_u8(c: &mut Criterion) {
             black_box(b);
         })
     });
+    group.finish();
 }
 
 //add the same benchmarks for u16
@@ -47,12 +51,13 @@ pub fn bench_binary_field_mul_u16(c: &mut Criterion) {
     let field = linearalgebra::binary::BinaryField::<u16>::new();
     group.bench_function("binary_field_mul_u16", |b| {
         b.iter(|| {
-            let a = 0x8f33;
-            let b = 0x33df;
+            let a = black_box(0x8f33);
+            let b = black_box(0x33df);
             let c = field.mul(&a, &b);
             black_box(c);
         })
     });
+    group.finish();
 }
 
 pub fn bench_binary_field_inv_u16(c: &mut Criterion) {
@@ -60,11 +65,12 @@ pub fn bench_binary_field_inv_u16(c: &mut Criterion) {
     let field = linearalgebra::binary::BinaryField::<u16>::new();
     group.bench_function("binary_field_inv_u16", |b| {
         b.iter(|| {
-            let a = 0x8f33;
+            let a = black_box(0x8f33);
             let b = field.inv(&a).unwrap();
             black_box(b);
         })
     });
+    group.finish();
 }
 
 pub fn bench_binary_field_inv_gcd_u16(c: &mut Criterion) {
@@ -77,6 +83,7 @@ pub fn bench_binary_field_inv_gcd_u16(c: &mut Criterion) {
             black_box(b);
         })
     });
+    group.finish();
 }
 
 //add the same benchmarks for u32
@@ -91,6 +98,7 @@ pub fn bench_binary_field_mul_u32(c: &mut Criterion) {
             black_box(c);
         })
     });
+    group.finish();
 }
 
 pub fn bench_binary_field_inv_u32(c: &mut Criterion) {
@@ -103,6 +111,7 @@ pub fn bench_binary_field_inv_u32(c: &mut Criterion) {
             black_box(b);
         })
     });
+    group.finish();
 }
 
 pub fn bench_binary_field_inv_gcd_u32(c: &mut Criterion) {
@@ -115,6 +124,7 @@ pub fn bench_binary_field_inv_gcd_u32(c: &mut Criterion) {
             black_box(b);
         })
     });
+    group.finish();
 }
 
 //add the same benchmarks for u64
@@ -129,6 +139,7 @@ pub fn bench_binary_field_mul_u64(c: &mut Criterion) {
             black_box(c);
         })
     });
+    group.finish();
 }
 
 pub fn bench_binary_field_inv_u64(c: &mut Criterion) {
@@ -141,6 +152,7 @@ pub fn bench_binary_field_inv_u64(c: &mut Criterion) {
             black_box(b);
         })
     });
+    group.finish();
 }
 
 pub fn bench_binary_field_inv_gcd_u64(c: &mut Criterion) {
@@ -153,6 +165,7 @@ pub fn bench_binary_field_inv_gcd_u64(c: &mut Criterion) {
             black_box(b);
         })
     });
+    group.finish();
 }
 
 pub fn bench_binary_field_mul_u128(c: &mut Criterion) {
@@ -166,6 +179,7 @@ pub fn bench_binary_field_mul_u128(c: &mut Criterion) {
             black_box(c);
         })
     });
+    group.finish();
 }
 
 pub fn bench_binary_field_inv_u128(c: &mut Criterion) {
@@ -178,6 +192,7 @@ pub fn bench_binary_field_inv_u128(c: &mut Criterion) {
             black_box(b);
         })
     });
+    group.finish();
 }
 
 pub fn bench_binary_field_inv_gcd_u128(c: &mut Criterion) {
@@ -190,6 +205,138 @@ pub fn bench_binary_field_inv_gcd_u128(c: &mut Criterion) {
             black_box(b);
         })
     });
+    group.finish();
+}
+
+//log/exp table backend against the bit-serial u8 and u16 fields above
+pub fn bench_table_field_mul_u8(c: &mut Criterion) {
+    let mut group = c.benchmark_group("binary_field_mul_u8");
+    let field = <LogTableField<u8> as Newable>::new();
+    group.bench_function("table_field_mul_u8", |b| {
+        b.iter(|| {
+            let a = black_box(0x8f);
+            let b = black_box(0x33);
+            let c = field.mul(&a, &b);
+            black_box(c);
+        })
+    });
+    group.finish();
+}
+
+pub fn bench_table_field_inv_u8(c: &mut Criterion) {
+    let mut group = c.benchmark_group("binary_field_inv_u8");
+    let field = <LogTableField<u8> as Newable>::new();
+    group.bench_function("table_field_inv_u8", |b| {
+        b.iter(|| {
+            let a = black_box(0x8f);
+            let b = field.inv(&a).unwrap();
+            black_box(b);
+        })
+    });
+    group.finish();
+}
+
+pub fn bench_table_field_mul_u16(c: &mut Criterion) {
+    let mut group = c.benchmark_group("binary_field_mul_u16");
+    let field = <LogTableField<u16> as Newable>::new();
+    group.bench_function("table_field_mul_u16", |b| {
+        b.iter(|| {
+            let a = black_box(0x8f33);
+            let b = black_box(0x33df);
+            let c = field.mul(&a, &b);
+            black_box(c);
+        })
+    });
+    group.finish();
+}
+
+pub fn bench_table_field_inv_u16(c: &mut Criterion) {
+    let mut group = c.benchmark_group("binary_field_inv_u16");
+    let field = <LogTableField<u16> as Newable>::new();
+    group.bench_function("table_field_inv_u16", |b| {
+        b.iter(|| {
+            let a = black_box(0x8f33);
+            let b = field.inv(&a).unwrap();
+            black_box(b);
+        })
+    });
+    group.finish();
+}
+
+//a row of an erasure code: multiply a 4K block by a constant and add it to another. The
+//constant goes through black_box so that the bit-serial loop cannot be specialised for it.
+pub fn bench_block_mul_add_u8(c: &mut Criterion) {
+    let mut group = c.benchmark_group("binary_field_block_mul_add_u8");
+    let field = linearalgebra::binary::BinaryField::<u8>::new();
+    let tables = <LogTableField<u8> as Newable>::new();
+    let source: Vec<u8> = (0..4096).map(|i| (i * 31 + 7) as u8).collect();
+    let mut target = vec![0u8; 4096];
+    let constant = black_box(0x8f);
+    group.bench_function("bit_serial", |b| {
+        b.iter(|| {
+            for (t, s) in target.iter_mut().zip(source.iter()) {
+                *t = field.add(t, &field.mul(&constant, s));
+            }
+            black_box(&target);
+        })
+    });
+    group.bench_function("log_tables", |b| {
+        b.iter(|| {
+            for (t, s) in target.iter_mut().zip(source.iter()) {
+                *t = tables.add(t, &tables.mul(&constant, s));
+            }
+            black_box(&target);
+        })
+    });
+    //built once per constant, like a row of the code matrix
+    let split = tables.split_table(&constant);
+    group.bench_function("split_table", |b| {
+        b.iter(|| {
+            split.mul_add_slice(&source, &mut target);
+            black_box(&target);
+        })
+    });
+    group.bench_function("split_table_build", |b| {
+        b.iter(|| black_box(tables.split_table(&constant)))
+    });
+    group.finish();
+}
+
+pub fn bench_block_mul_add_u16(c: &mut Criterion) {
+    let mut group = c.benchmark_group("binary_field_block_mul_add_u16");
+    let field = linearalgebra::binary::BinaryField::<u16>::new();
+    let tables = <LogTableField<u16> as Newable>::new();
+    let source: Vec<u16> = (0..4096).map(|i| (i * 7919 + 7) as u16).collect();
+    let mut target = vec![0u16; 4096];
+    let constant = black_box(0x8f33);
+    group.bench_function("bit_serial", |b| {
+        b.iter(|| {
+            for (t, s) in target.iter_mut().zip(source.iter()) {
+                *t = field.add(t, &field.mul(&constant, s));
+            }
+            black_box(&target);
+        })
+    });
+    group.bench_function("log_tables", |b| {
+        b.iter(|| {
+            for (t, s) in target.iter_mut().zip(source.iter()) {
+                *t = tables.add(t, &tables.mul(&constant, s));
+            }
+            black_box(&target);
+        })
+    });
+    //built once per constant, like a row of the code matrix
+    let split = tables.split_table(&constant);
+    group.bench_function("split_table", |b| {
+        b.iter(|| {
+            split.mul_add_slice(&source, &mut target);
+            black_box(&target);
+        })
+    });
+    group.bench_function("split_table_build", |b| {
+        b.iter(|| black_box(tables.split_table(&constant)))
+    });
+    group.finish();
 }
 
 criterion_group!(
@@ -212,6 +359,13 @@ criterion_group!(
     //add the same benchmarks for u128
     bench_binary_field_inv_gcd_u128,
     bench_binary_field_inv_u128,
-    bench_binary_field_mul_u128
+    bench_binary_field_mul_u128,
+    //log/exp table backend
+    bench_table_field_mul_u8,
+    bench_table_field_inv_u8,
+    bench_table_field_mul_u16,
+    bench_table_field_inv_u16,
+    bench_block_mul_add_u8,
+    bench_block_mul_add_u16
 );
 criterion_main!(benches);
@@ -0,0 +1,190 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use linearalgebra::field::{F64Field, ModularField};
+use linearalgebra::{Field, Matrix, Ring};
+use num_bigint::BigUint;
+
+fn f64_rows(size: usize) -> Vec<Vec<f64>> {
+    (0..size)
+        .map(|i| {
+            (0..size)
+                .map(|j| ((i * 31 + j * 17) % 101) as f64 + if i == j { size as f64 } else { 0.0 })
+                .collect()
+        })
+        .collect()
+}
+
+fn modular_rows(size: usize) -> Vec<Vec<BigUint>> {
+    (0..size)
+        .map(|i| {
+            (0..size)
+                .map(|j| BigUint::from(((i * 31 + j * 17) % 101 + (i == j) as usize) as u64))
+                .collect()
+        })
+        .collect()
+}
+
+fn f64_matrix(ring: &F64Field, size: usize) -> Matrix<'_, F64Field> {
+    Matrix::new(ring, f64_rows(size))
+}
+
+fn modular_matrix(ring: &ModularField, size: usize) -> Matrix<'_, ModularField> {
+    Matrix::new(ring, modular_rows(size))
+}
+
+//The previous layout with one Vec per row and the same algorithms, so that every group
+//measures the flat buffer against it
+mod nested {
+    use super::*;
+
+    pub fn mul<R: Ring>(
+        ring: &R,
+        lhs: &[Vec<R::RingMember>],
+        rhs: &[Vec<R::RingMember>],
+    ) -> Vec<Vec<R::RingMember>> {
+        let mut ans = vec![vec![ring.zero(); rhs[0].len()]; lhs.len()];
+        for i in 0..lhs.len() {
+            for j in 0..rhs[0].len() {
+                for k in 0..rhs.len() {
+                    let prod = ring.mul(&lhs[i][k], &rhs[k][j]);
+                    ans[i][j] = ring.add(&ans[i][j], &prod);
+                }
+            }
+        }
+        ans
+    }
+
+    pub fn transpose<R: Ring>(ring: &R, data: &[Vec<R::RingMember>]) -> Vec<Vec<R::RingMember>> {
+        let rows = data[0].len();
+        let columns = data.len();
+        let mut ans = vec![vec![ring.zero(); columns]; rows];
+        for j in 0..columns {
+            for i in 0..rows {
+                ans[i][j] = data[j][i].clone();
+            }
+        }
+        ans
+    }
+
+    fn add_multiple_of<F: Field>(
+        ring: &F,
+        data: &mut [Vec<F::RingMember>],
+        r1: usize,
+        r2: usize,
+        mul: &F::RingMember,
+    ) {
+        for i in 0..data[r1].len() {
+            data[r1][i] = ring.add(&data[r1][i], &ring.mul(mul, &data[r2][i]));
+        }
+    }
+
+    //Gauss-Jordan on the rows of data and the identity side by side, first non-zero pivot
+    pub fn inverse<F: Field>(ring: &F, data: &[Vec<F::RingMember>]) -> Vec<Vec<F::RingMember>> {
+        let n = data.len();
+        let mut data1 = data.to_vec();
+        let mut data2: Vec<Vec<F::RingMember>> = (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| if i == j { ring.one() } else { ring.zero() })
+                    .collect()
+            })
+            .collect();
+        for i in 0..n {
+            let p = (i..n).find(|&p| data1[p][i] != ring.zero()).unwrap();
+            data1.swap(i, p);
+            data2.swap(i, p);
+            let d_inv = ring.inv(&data1[i][i]).unwrap();
+            for row in [&mut data1[i], &mut data2[i]] {
+                for x in row.iter_mut() {
+                    *x = ring.mul(&d_inv, x);
+                }
+            }
+            for j in 0..n {
+                if j != i {
+                    let mult = ring.neg(&data1[j][i]);
+                    add_multiple_of(ring, &mut data2, j, i, &mult);
+                    add_multiple_of(ring, &mut data1, j, i, &mult);
+                }
+            }
+        }
+        data2
+    }
+}
+
+pub fn bench_matrix_mul_f64(c: &mut Criterion) {
+    let mut group = c.benchmark_group("matrix_mul_f64");
+    let ring = F64Field;
+    let lhs = f64_matrix(&ring, 64);
+    let rhs = lhs.transpose();
+    group.bench_function("matrix_mul_f64_64", |b| {
+        b.iter(|| {
+            let c = lhs.mul(&rhs).unwrap();
+            black_box(c);
+        })
+    });
+    let nested_lhs = f64_rows(64);
+    let nested_rhs = nested::transpose(&ring, &nested_lhs);
+    group.bench_function("nested_vec_mul_f64_64", |b| {
+        b.iter(|| black_box(nested::mul(&ring, &nested_lhs, &nested_rhs)))
+    });
+    group.finish();
+}
+
+pub fn bench_matrix_transpose_f64(c: &mut Criterion) {
+    let mut group = c.benchmark_group("matrix_transpose_f64");
+    let ring = F64Field;
+    let mat = f64_matrix(&ring, 256);
+    group.bench_function("matrix_transpose_f64_256", |b| {
+        b.iter(|| {
+            let t = mat.transpose();
+            black_box(t);
+        })
+    });
+    let nested_mat = f64_rows(256);
+    group.bench_function("nested_vec_transpose_f64_256", |b| {
+        b.iter(|| black_box(nested::transpose(&ring, &nested_mat)))
+    });
+    group.finish();
+}
+
+pub fn bench_matrix_inverse_f64(c: &mut Criterion) {
+    let mut group = c.benchmark_group("matrix_inverse_f64");
+    let ring = F64Field;
+    let mat = f64_matrix(&ring, 64);
+    group.bench_function("matrix_inverse_f64_64", |b| {
+        b.iter(|| {
+            let inv = mat.inverse().unwrap();
+            black_box(inv);
+        })
+    });
+    let nested_mat = f64_rows(64);
+    group.bench_function("nested_vec_inverse_f64_64", |b| {
+        b.iter(|| black_box(nested::inverse(&ring, &nested_mat)))
+    });
+    group.finish();
+}
+
+pub fn bench_matrix_inverse_modular(c: &mut Criterion) {
+    let mut group = c.benchmark_group("matrix_inverse_modular");
+    let ring = ModularField::new(BigUint::from(1_000_000_007u64));
+    let mat = modular_matrix(&ring, 32);
+    group.bench_function("matrix_inverse_modular_32", |b| {
+        b.iter(|| {
+            let inv = mat.inverse().unwrap();
+            black_box(inv);
+        })
+    });
+    let nested_mat = modular_rows(32);
+    group.bench_function("nested_vec_inverse_modular_32", |b| {
+        b.iter(|| black_box(nested::inverse(&ring, &nested_mat)))
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_matrix_mul_f64,
+    bench_matrix_transpose_f64,
+    bench_matrix_inverse_f64,
+    bench_matrix_inverse_modular
+);
+criterion_main!(benches);
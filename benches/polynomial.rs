@@ -0,0 +1,81 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use linearalgebra::field::ModularField;
+use linearalgebra::polynomial::{Polynomial, PolynomialRing};
+use linearalgebra::Ring;
+use num_bigint::BigUint;
+
+//998244353 = 119 * 2^23 + 1 supports the number theoretic transform, 1000000007 does not
+//beyond order 2
+const NTT_PRIME: u64 = 998244353;
+const PLAIN_PRIME: u64 = 1000000007;
+
+fn polynomial(
+    ring: &PolynomialRing<ModularField>,
+    degree: usize,
+    seed: u64,
+) -> Polynomial<ModularField> {
+    ring.polynomial(
+        (0..=degree as u64)
+            .map(|i| BigUint::from((i * 7919 + seed) % 1000003 + 1))
+            .collect(),
+    )
+}
+
+//Schoolbook and Karatsuba get three and four times slower with every doubling, so above
+//these degrees only the transform is measured
+const SCHOOLBOOK_MAX_DEGREE: usize = 2047;
+const KARATSUBA_MAX_DEGREE: usize = 16383;
+
+//degrees 2^k - 1 from 31 up to 131071, which shows the Karatsuba/NTT crossover at the small end
+//and the scaling of the transform up to degree ~10^5
+pub fn bench_polynomial_mul(c: &mut Criterion) {
+    let mut group = c.benchmark_group("polynomial_mul");
+    group.sample_size(10);
+    let field = ModularField::new(BigUint::from(NTT_PRIME));
+    let ring = PolynomialRing::new(&field);
+    for k in 5..=17 {
+        let degree = (1usize << k) - 1;
+        let a = polynomial(&ring, degree, 1);
+        let b = polynomial(&ring, degree, 2);
+        if degree <= SCHOOLBOOK_MAX_DEGREE {
+            group.bench_function(format!("schoolbook_{}", degree), |bencher| {
+                bencher.iter(|| black_box(ring.mul_schoolbook(&a, &b)))
+            });
+        }
+        if degree <= KARATSUBA_MAX_DEGREE {
+            group.bench_function(format!("karatsuba_{}", degree), |bencher| {
+                bencher.iter(|| black_box(ring.mul_karatsuba(&a, &b)))
+            });
+        }
+        group.bench_function(format!("ntt_{}", degree), |bencher| {
+            bencher.iter(|| black_box(ring.mul_ntt(&a, &b).unwrap()))
+        });
+    }
+    group.finish();
+}
+
+pub fn bench_polynomial_mul_auto(c: &mut Criterion) {
+    let mut group = c.benchmark_group("polynomial_mul_auto");
+    group.sample_size(10);
+    for prime in [NTT_PRIME, PLAIN_PRIME] {
+        let field = ModularField::new(BigUint::from(prime));
+        let ring = PolynomialRing::new(&field);
+        for degree in [2047, 16383] {
+            let a = polynomial(&ring, degree, 1);
+            let b = polynomial(&ring, degree, 2);
+            group.bench_function(format!("mul_{}_mod_{}", degree, prime), |bencher| {
+                bencher.iter(|| black_box(ring.mul(&a, &b)))
+            });
+            //the quadratic product for reference, as long as it finishes in reasonable time
+            if degree <= SCHOOLBOOK_MAX_DEGREE {
+                group.bench_function(format!("schoolbook_{}_mod_{}", degree, prime), |bencher| {
+                    bencher.iter(|| black_box(ring.mul_schoolbook(&a, &b)))
+                });
+            }
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_polynomial_mul, bench_polynomial_mul_auto);
+criterion_main!(benches);
@@ -0,0 +1,85 @@
+/*
+ * Copyright  2020 Debasish Ray Chawdhuri
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do
+ * so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+ * EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+ * MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT
+ * HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+ * WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+ * DEALINGS IN THE SOFTWARE.
+ */
+use num_bigint::BigUint;
+use num_integer::Integer;
+
+pub(crate) const SMALL_PRIMES: [u64; 25] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+];
+
+//Miller-Rabin with the small primes as bases, which is deterministic below 3.3 * 10^24 and
+//overwhelmingly likely to be right above that
+pub(crate) fn is_probable_prime(n: &BigUint) -> bool {
+    let one = BigUint::from(1u64);
+    if *n <= one {
+        return false;
+    }
+    for &p in SMALL_PRIMES.iter() {
+        let p = BigUint::from(p);
+        if *n == p {
+            return true;
+        }
+        if (n % &p) == BigUint::from(0u64) {
+            return false;
+        }
+    }
+    let n_minus_one = n - &one;
+    let mut d = n_minus_one.clone();
+    let mut s = 0;
+    while d.is_even() {
+        d >>= 1usize;
+        s += 1;
+    }
+    'witness: for &a in SMALL_PRIMES.iter().take(13) {
+        let mut x = BigUint::from(a).modpow(&d, n);
+        if x == one || x == n_minus_one {
+            continue;
+        }
+        for _ in 1..s {
+            x = (&x * &x) % n;
+            if x == n_minus_one {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_probable_prime() {
+        let primes: Vec<u64> = (0..100u64)
+            .filter(|&n| is_probable_prime(&BigUint::from(n)))
+            .collect();
+        assert_eq!(primes, SMALL_PRIMES.to_vec());
+        //Carmichael numbers and a strong pseudoprime to base 2
+        for &n in [561u64, 1105, 2047, 3215031751].iter() {
+            assert!(!is_probable_prime(&BigUint::from(n)));
+        }
+        assert!(is_probable_prime(&BigUint::from(998244353u64)));
+    }
+}
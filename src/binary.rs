@@ -31,6 +31,7 @@ use crate::{
     euclidian_domain::{DivisionAlgorithmResult, EuclidianDomain},
     Field, Ring,
 };
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub struct BinaryRing<T: Unsigned> {
     _phantom: PhantomData<T>,
 }
@@ -147,6 +148,7 @@ impl<T: Unsigned> BinaryRing<T> {
             }
         }
     }
+    #[allow(clippy::ptr_arg)]
     pub fn shift_right(value: &mut Vec<T>) {
         for i in 0..value.len() {
             value[i] >>= 1;
@@ -163,7 +165,13 @@ impl<T: Unsigned> BinaryRing<T> {
         value[num_of_units] & mask != T::ZERO
     }
 
+    #[allow(clippy::ptr_arg)]
     pub fn degree(&self, value: &Vec<T>) -> i32 {
+        Self::slice_degree(value)
+    }
+
+    //degree of a borrowed polynomial, which callers need not copy into a Vec first
+    pub(crate) fn slice_degree(value: &[T]) -> i32 {
         let len = value.len();
         if len == 0 {
             return -1;
@@ -213,17 +221,28 @@ impl<T: Unsigned> EuclidianDomain for BinaryRing<T> {
         value: &Self::RingMember,
         divisor: &Self::RingMember,
     ) -> Result<DivisionAlgorithmResult<Self::RingMember>, Error> {
-        let v_deg = self.degree(value);
-        let d_deg = self.degree(divisor);
+        self.divide_slices(value, divisor)
+    }
+}
 
-        let mut substractor = divisor.clone();
+impl<T: Unsigned> BinaryRing<T> {
+    //division_algorithm on borrowed operands
+    pub(crate) fn divide_slices(
+        &self,
+        value: &[T],
+        divisor: &[T],
+    ) -> Result<DivisionAlgorithmResult<Vec<T>>, Error> {
+        let v_deg = Self::slice_degree(value);
+        let d_deg = Self::slice_degree(divisor);
+
+        let mut substractor = divisor.to_vec();
         BinaryRing::clean_up(&mut substractor);
 
         if substractor == self.zero() {
             return Err(Error::DivisionByZero);
         }
 
-        let mut value = value.clone();
+        let mut value = value.to_vec();
         if v_deg < d_deg {
             Ok(DivisionAlgorithmResult {
                 quotient: vec![],
@@ -268,6 +287,25 @@ impl<T: Unsigned> Ring for BinaryRing<T> {
     }
 
     fn mul(&self, lhs: &Self::RingMember, rhs: &Self::RingMember) -> Self::RingMember {
+        Self::mul_slices(lhs, rhs)
+    }
+
+    fn neg(&self, lhs: &Self::RingMember) -> Self::RingMember {
+        lhs.clone()
+    }
+
+    fn zero(&self) -> Self::RingMember {
+        vec![]
+    }
+
+    fn one(&self) -> Self::RingMember {
+        vec![T::ONE]
+    }
+}
+
+impl<T: Unsigned> BinaryRing<T> {
+    //Ring::mul on borrowed operands
+    pub(crate) fn mul_slices(lhs: &[T], rhs: &[T]) -> Vec<T> {
         if lhs.is_empty() || rhs.is_empty() {
             return vec![];
         }
@@ -282,20 +320,9 @@ impl<T: Unsigned> Ring for BinaryRing<T> {
         Self::clean_up(&mut result);
         result
     }
-
-    fn neg(&self, lhs: &Self::RingMember) -> Self::RingMember {
-        lhs.clone()
-    }
-
-    fn zero(&self) -> Self::RingMember {
-        vec![]
-    }
-
-    fn one(&self) -> Self::RingMember {
-        vec![T::ONE]
-    }
 }
 
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub struct BinaryField<T: Unsigned> {
     _mod_substractor: T,
 }
@@ -337,6 +364,8 @@ impl Newable for BinaryField<u32> {
     }
 }
 
+//x^64 + x^4 + x^3 + x + 1, the lowest weight irreducible polynomial of degree 64 as there is no
+//irreducible trinomial
 impl Newable for BinaryField<u64> {
     fn new() -> Self {
         BinaryField::new_with_mod_substractor(0b11011)
@@ -355,6 +384,21 @@ impl<T: Unsigned> BinaryField<T> {
         }
     }
 
+    pub fn mod_substractor(&self) -> T {
+        self._mod_substractor
+    }
+
+    //Same as new_with_mod_substractor, but checks that x^BITS + mod_substractor is irreducible,
+    //since otherwise the result is not a field
+    pub fn try_new_with_mod_substractor(mod_substractor: T) -> Result<Self, Error> {
+        let ring = BinaryRing::<T>::new();
+        if ring.is_irreducible(&[mod_substractor, T::ONE]) {
+            Ok(Self::new_with_mod_substractor(mod_substractor))
+        } else {
+            Err(Error::ReducibleBinaryFieldModulus)
+        }
+    }
+
     #[allow(unused)]
     fn degree(value: &T) -> u32 {
         let mut mask = T::ONE << (T::BITS - 1);
@@ -541,7 +585,45 @@ impl<T: Unsigned> Field for BinaryField<T> {
 mod tests {
     use crate::Ring;
 
-    use super::{BinaryRing, BitIterator};
+    use super::{BinaryField, BinaryRing, BitIterator, Newable};
+    use crate::error::Error;
+    use crate::Field;
+    use funty::Unsigned;
+
+    pub fn check_field_axioms<T: Unsigned>(field: &BinaryField<T>, a: T, b: T, c: T) {
+        assert_eq!(field.mul(&a, &b), field.mul(&b, &a));
+        assert_eq!(
+            field.mul(&field.mul(&a, &b), &c),
+            field.mul(&a, &field.mul(&b, &c))
+        );
+        assert_eq!(
+            field.mul(&a, &field.add(&b, &c)),
+            field.add(&field.mul(&a, &b), &field.mul(&a, &c))
+        );
+        assert_eq!(field.mul(&a, &field.one()), a);
+        if a != T::ZERO {
+            let inv = field.inv(&a).unwrap();
+            assert_eq!(field.mul(&a, &inv), T::ONE);
+            assert_eq!(field.gcd_inv(&a).unwrap(), inv);
+        }
+    }
+
+    #[test]
+    fn test_checked_constructor() {
+        //x^8 + x^4 + x^3 + x^2 + 1 is irreducible, x^8 + x^4 + x^3 + x and x^8 are not
+        assert!(BinaryField::try_new_with_mod_substractor(0b11101u8).is_ok());
+        assert!(matches!(
+            BinaryField::try_new_with_mod_substractor(0b11010u8),
+            Err(Error::ReducibleBinaryFieldModulus)
+        ));
+        assert!(BinaryField::try_new_with_mod_substractor(0u8).is_err());
+        assert_eq!(
+            BinaryField::try_new_with_mod_substractor(0b11011u8).unwrap(),
+            <BinaryField<u8> as Newable>::new()
+        );
+        assert!(BinaryField::try_new_with_mod_substractor(0b11011u64).is_ok());
+        assert!(BinaryField::try_new_with_mod_substractor(0b10000111u128).is_ok());
+    }
 
     #[test]
     fn test_bit_iterator() {
@@ -667,6 +749,7 @@ proptest! {
         }
     }
     #[test]
+    #[allow(clippy::redundant_locals)]
     fn test_mul_div_mod(a:u8){
         let a = a;
         if a>1 {
@@ -768,4 +851,24 @@ proptest! {
         }
     }
 
+    #[test]
+    fn test_field_axioms_u8(a:u8, b:u8, c:u8){
+        tests::check_field_axioms(&BinaryField::new(), a, b, c);
+    }
+    #[test]
+    fn test_field_axioms_u16(a:u16, b:u16, c:u16){
+        tests::check_field_axioms(&BinaryField::new(), a, b, c);
+    }
+    #[test]
+    fn test_field_axioms_u32(a:u32, b:u32, c:u32){
+        tests::check_field_axioms(&BinaryField::new(), a, b, c);
+    }
+    #[test]
+    fn test_field_axioms_u64(a:u64, b:u64, c:u64){
+        tests::check_field_axioms(&BinaryField::new(), a, b, c);
+    }
+    #[test]
+    fn test_field_axioms_u128(a:u128, b:u128, c:u128){
+        tests::check_field_axioms(&BinaryField::new(), a, b, c);
+    }
 }
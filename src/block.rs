@@ -0,0 +1,257 @@
+/*
+ * Copyright  2020 Debasish Ray Chawdhuri
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do
+ * so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+ * EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+ * MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT
+ * HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+ * WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+ * DEALINGS IN THE SOFTWARE.
+ */
+use crate::error::Error;
+use crate::{Matrix, Ring};
+use std::ops::{Index, Range};
+
+//Borrowed rectangular window into a matrix. Nothing is copied until to_matrix is called.
+pub struct MatrixView<'m, 'a, F: Ring> {
+    matrix: &'m Matrix<'a, F>,
+    row_offset: usize,
+    col_offset: usize,
+    rows: usize,
+    columns: usize,
+}
+
+impl<'m, 'a, F: Ring> Clone for MatrixView<'m, 'a, F> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'m, 'a, F: Ring> Copy for MatrixView<'m, 'a, F> {}
+
+fn check_range(range: &Range<usize>, len: usize) {
+    assert!(
+        range.start <= range.end && range.end <= len,
+        "range {:?} out of range for dimension {}",
+        range,
+        len
+    );
+}
+
+impl<'m, 'a, F: Ring> MatrixView<'m, 'a, F> {
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn value_at(&self, row: usize, col: usize) -> F::RingMember {
+        self[(row, col)].clone()
+    }
+
+    pub fn row(&self, row: usize) -> &'m [F::RingMember] {
+        assert!(row < self.rows, "row {} out of range", row);
+        &self.matrix.row(self.row_offset + row)[self.col_offset..self.col_offset + self.columns]
+    }
+
+    pub fn row_iter(&self) -> impl Iterator<Item = &'m [F::RingMember]> + '_ {
+        (0..self.rows).map(move |i| self.row(i))
+    }
+
+    //a view of a view still points straight into the parent matrix
+    pub fn view(&self, rows: Range<usize>, cols: Range<usize>) -> MatrixView<'m, 'a, F> {
+        check_range(&rows, self.rows);
+        check_range(&cols, self.columns);
+        MatrixView {
+            matrix: self.matrix,
+            row_offset: self.row_offset + rows.start,
+            col_offset: self.col_offset + cols.start,
+            rows: rows.len(),
+            columns: cols.len(),
+        }
+    }
+
+    pub fn to_matrix(&self) -> Matrix<'a, F> {
+        Matrix {
+            ring: self.matrix.ring.clone(),
+            rows: self.rows,
+            columns: self.columns,
+            data: self.row_iter().flatten().cloned().collect(),
+        }
+    }
+}
+
+impl<'m, 'a, F: Ring> Index<(usize, usize)> for MatrixView<'m, 'a, F> {
+    type Output = F::RingMember;
+
+    fn index(&self, (row, col): (usize, usize)) -> &F::RingMember {
+        &self.row(row)[col]
+    }
+}
+
+impl<'a, F: Ring> Matrix<'a, F> {
+    pub fn view(&self, rows: Range<usize>, cols: Range<usize>) -> MatrixView<'_, 'a, F> {
+        check_range(&rows, self.rows);
+        check_range(&cols, self.columns);
+        MatrixView {
+            matrix: self,
+            row_offset: rows.start,
+            col_offset: cols.start,
+            rows: rows.len(),
+            columns: cols.len(),
+        }
+    }
+
+    pub fn submatrix(&self, rows: Range<usize>, cols: Range<usize>) -> Matrix<'a, F> {
+        self.view(rows, cols).to_matrix()
+    }
+
+    //[self rhs]
+    pub fn hstack(&self, rhs: &Matrix<F>) -> Result<Matrix<'a, F>, Error> {
+        if self.rows != rhs.rows {
+            return Err(Error::DimensionMismatchForConcatenation(
+                self.rows,
+                self.columns,
+                rhs.rows,
+                rhs.columns,
+            ));
+        }
+        let mut data = Vec::with_capacity(self.data.len() + rhs.data.len());
+        for i in 0..self.rows {
+            data.extend_from_slice(self.row(i));
+            data.extend_from_slice(rhs.row(i));
+        }
+        Ok(Matrix {
+            ring: self.ring.clone(),
+            rows: self.rows,
+            columns: self.columns + rhs.columns,
+            data,
+        })
+    }
+
+    //[self; rhs]
+    pub fn vstack(&self, rhs: &Matrix<F>) -> Result<Matrix<'a, F>, Error> {
+        if self.columns != rhs.columns {
+            return Err(Error::DimensionMismatchForConcatenation(
+                self.rows,
+                self.columns,
+                rhs.rows,
+                rhs.columns,
+            ));
+        }
+        let mut data = Vec::with_capacity(self.data.len() + rhs.data.len());
+        data.extend_from_slice(&self.data);
+        data.extend_from_slice(&rhs.data);
+        Ok(Matrix {
+            ring: self.ring.clone(),
+            rows: self.rows + rhs.rows,
+            columns: self.columns,
+            data,
+        })
+    }
+
+    //[a b; c d]
+    pub fn from_blocks(
+        a: &Matrix<'a, F>,
+        b: &Matrix<F>,
+        c: &Matrix<F>,
+        d: &Matrix<F>,
+    ) -> Result<Matrix<'a, F>, Error> {
+        a.hstack(b)?.vstack(&c.hstack(d)?)
+    }
+
+    //The blocks are placed along the diagonal with zeros everywhere else
+    pub fn block_diagonal(blocks: &[&Matrix<'a, F>]) -> Matrix<'a, F> {
+        assert!(
+            !blocks.is_empty(),
+            "block_diagonal needs at least one block"
+        );
+        let rows: usize = blocks.iter().map(|b| b.rows).sum();
+        let columns: usize = blocks.iter().map(|b| b.columns).sum();
+        let mut result = Matrix::zeros(blocks[0].ring.clone(), rows, columns);
+        let (mut row_offset, mut col_offset) = (0, 0);
+        for block in blocks {
+            for i in 0..block.rows {
+                result.row_mut(row_offset + i)[col_offset..col_offset + block.columns]
+                    .clone_from_slice(block.row(i));
+            }
+            row_offset += block.rows;
+            col_offset += block.columns;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::int_ring::I32Ring;
+    use crate::matrix;
+
+    #[test]
+    fn test_views() {
+        let ring = I32Ring;
+        let mat = matrix!(&ring, [1, 2, 3, 4; 5, 6, 7, 8; 9, 10, 11, 12]);
+        let view = mat.view(1..3, 1..4);
+        assert_eq!((view.rows(), view.columns()), (2, 3));
+        assert_eq!(view[(1, 2)], 12);
+        assert_eq!(view.row(0), &[6, 7, 8]);
+        let inner = view.view(0..2, 1..2);
+        assert_eq!(inner.to_matrix(), matrix!(&ring, [7; 11]));
+        assert_eq!(mat.submatrix(0..1, 0..2), matrix!(&ring, [1, 2]));
+        assert_eq!(mat.submatrix(0..0, 0..2).rows(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_view_out_of_range() {
+        let ring = I32Ring;
+        let mat = matrix!(&ring, [1, 2; 3, 4]);
+        mat.view(0..3, 0..1);
+    }
+
+    #[test]
+    fn test_stacking() {
+        let ring = I32Ring;
+        let a = matrix!(&ring, [1, 2; 3, 4]);
+        let b = matrix!(&ring, [5; 6]);
+        let c = matrix!(&ring, [7, 8]);
+        let d = matrix!(&ring, [9]);
+        assert_eq!(a.hstack(&b).unwrap(), matrix!(&ring, [1, 2, 5; 3, 4, 6]));
+        assert_eq!(a.vstack(&c).unwrap(), matrix!(&ring, [1, 2; 3, 4; 7, 8]));
+        assert!(matches!(
+            a.hstack(&c),
+            Err(Error::DimensionMismatchForConcatenation(2, 2, 1, 2))
+        ));
+        assert!(a.vstack(&b).is_err());
+        let blocks = Matrix::from_blocks(&a, &b, &c, &d).unwrap();
+        assert_eq!(blocks, matrix!(&ring, [1, 2, 5; 3, 4, 6; 7, 8, 9]));
+        assert_eq!(blocks.submatrix(0..2, 0..2), a);
+        assert!(Matrix::from_blocks(&a, &c, &b, &d).is_err());
+    }
+
+    #[test]
+    fn test_block_diagonal() {
+        let ring = I32Ring;
+        let a = matrix!(&ring, [1, 2; 3, 4]);
+        let b = matrix!(&ring, [5, 6, 7]);
+        assert_eq!(
+            Matrix::block_diagonal(&[&a, &b]),
+            matrix!(&ring, [1, 2, 0, 0, 0; 3, 4, 0, 0, 0; 0, 0, 5, 6, 7])
+        );
+    }
+}
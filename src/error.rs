@@ -6,8 +6,26 @@ pub enum Error {
     InversionOfZero,
     InversionOfNonInvertibleSquareMatrix,
     InversionOfRectangularMatrix,
+    DeterminantOfRectangularMatrix,
+    DecompositionOfRectangularMatrix,
+    PolynomialOfRectangularMatrix,
     DimensionMismatchForMatrixAddition(usize, usize, usize, usize),
     DimensionMismatchForMatrixMultiplication(usize, usize, usize, usize),
+    DimensionMismatchForLinearSystem(usize, usize, usize, usize),
+    DimensionMismatchForConcatenation(usize, usize, usize, usize),
+    DimensionMismatchForVectors(usize, usize),
+    ConversionOfNonVectorMatrix(usize, usize),
+    InconsistentLinearSystem,
+    LinearlyDependentLatticeBasis,
+    LllDeltaOutOfRange,
+    RepeatedInterpolationNode,
+    FactorizationOfZeroPolynomial,
+    FactorizationOverNonPrimeModulus,
+    InvalidFactorDegree(usize, usize),
+    SplittingAttemptsExhausted,
+    ReducibleBinaryFieldModulus,
+    BinaryFieldTooWideForTables(u32),
+    LogarithmOfZero,
 }
 
 impl StdError for Error {}
@@ -23,6 +41,21 @@ impl Display for Error {
             Error::InversionOfRectangularMatrix => {
                 write!(f, "Error trying to invert a rectangular matrix")
             }
+            Error::DeterminantOfRectangularMatrix => {
+                write!(
+                    f,
+                    "Error trying to find the determinant of a rectangular matrix"
+                )
+            }
+            Error::DecompositionOfRectangularMatrix => {
+                write!(f, "Error trying to decompose a rectangular matrix")
+            }
+            Error::PolynomialOfRectangularMatrix => {
+                write!(
+                    f,
+                    "Error trying to find a characteristic or minimal polynomial of a rectangular matrix"
+                )
+            }
             Error::DimensionMismatchForMatrixAddition(rows1, cols1, rows2, cols2) => {
                 write!(
                     f,
@@ -39,6 +72,77 @@ impl Display for Error {
                     rows1, cols1, rows2, cols2
                 )
             }
+            Error::DimensionMismatchForLinearSystem(rows1, cols1, rows2, cols2) => {
+                write!(
+                    f,
+                    "Error trying to solve a linear system with a ({}, {}) coefficient matrix \
+                    and a ({}, {}) right hand side",
+                    rows1, cols1, rows2, cols2
+                )
+            }
+            Error::DimensionMismatchForConcatenation(rows1, cols1, rows2, cols2) => {
+                write!(
+                    f,
+                    "Error trying to concatenate two matrices of incompatible dimensions: \
+                    ({}, {}) and ({}, {})",
+                    rows1, cols1, rows2, cols2
+                )
+            }
+            Error::DimensionMismatchForVectors(len1, len2) => {
+                write!(
+                    f,
+                    "Error trying to combine vectors of different lengths: {} and {}",
+                    len1, len2
+                )
+            }
+            Error::ConversionOfNonVectorMatrix(rows, cols) => {
+                write!(
+                    f,
+                    "Error trying to convert a ({}, {}) matrix into a vector",
+                    rows, cols
+                )
+            }
+            Error::InconsistentLinearSystem => {
+                write!(f, "The linear system has no solution")
+            }
+            Error::LinearlyDependentLatticeBasis => {
+                write!(
+                    f,
+                    "Error trying to reduce a linearly dependent lattice basis"
+                )
+            }
+            Error::LllDeltaOutOfRange => {
+                write!(f, "The LLL parameter delta must be in (1/4, 1]")
+            }
+            Error::RepeatedInterpolationNode => {
+                write!(f, "Error trying to interpolate through a repeated point")
+            }
+            Error::FactorizationOfZeroPolynomial => {
+                write!(f, "Error trying to factor the zero polynomial")
+            }
+            Error::FactorizationOverNonPrimeModulus => {
+                write!(f, "Polynomials can only be factored modulo a prime")
+            }
+            Error::InvalidFactorDegree(n, d) => write!(
+                f,
+                "A polynomial of degree {} has no factorization into factors of degree {}",
+                n, d
+            ),
+            Error::SplittingAttemptsExhausted => write!(
+                f,
+                "Error trying to split a polynomial that is not a product of equal degree factors"
+            ),
+            Error::ReducibleBinaryFieldModulus => {
+                write!(f, "The modulus of a binary field must be irreducible")
+            }
+            Error::BinaryFieldTooWideForTables(bits) => {
+                write!(
+                    f,
+                    "Binary field of {} bits is too wide for log tables",
+                    bits
+                )
+            }
+            Error::LogarithmOfZero => write!(f, "Attempt to take the logarithm of zero"),
         }
     }
 }
@@ -40,6 +40,13 @@ pub trait EuclidianDomain: Ring {
         divisor: &Self::RingMember,
     ) -> Result<DivisionAlgorithmResult<Self::RingMember>, Error>;
 
+    //Unit u such that u * value is the canonical associate of value, e.g. the absolute value
+    //for integers. Rings whose only unit is one can keep the default.
+    #[allow(unused)]
+    fn normalization_unit(&self, value: &Self::RingMember) -> Self::RingMember {
+        self.one()
+    }
+
     fn extended_euclid(
         &self,
         a: &Self::RingMember,
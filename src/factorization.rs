@@ -0,0 +1,493 @@
+/*
+ * Copyright  2020 Debasish Ray Chawdhuri
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do
+ * so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+ * EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+ * MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT
+ * HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+ * WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+ * DEALINGS IN THE SOFTWARE.
+ */
+use crate::binary::BinaryRing;
+use crate::error::Error;
+use crate::euclidian_domain::EuclidianDomain;
+use crate::field::ModularField;
+use crate::polynomial::{Polynomial, PolynomialRing};
+use crate::Ring;
+use funty::Unsigned;
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+
+//Each random attempt splits a polynomial with at least two equal degree factors with
+//probability about one half, so running out of attempts means the input was not such a product
+const MAX_SPLITTING_ATTEMPTS: usize = 64;
+
+//xorshift generator for the random polynomials of Cantor-Zassenhaus. A fixed seed keeps
+//factorizations reproducible.
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, bound: &BigUint) -> BigUint {
+        let words = bound.bits() / 64 + 2;
+        let mut value = BigUint::from(0u64);
+        for _ in 0..words {
+            value = (value << 64usize) + BigUint::from(self.next());
+        }
+        value % bound
+    }
+}
+
+//Polynomials over GF(p). The modulus of the field has to be prime.
+impl<'a> PolynomialRing<'a, ModularField> {
+    fn exact_quotient(
+        &self,
+        value: &Polynomial<ModularField>,
+        divisor: &Polynomial<ModularField>,
+    ) -> Polynomial<ModularField> {
+        //only used with a non-zero divisor
+        self.division_algorithm(value, divisor).unwrap().quotient
+    }
+
+    //all of the algorithms below rely on GF(p) being a field
+    fn check_prime_modulus(&self) -> Result<(), Error> {
+        if self.underlying_field().is_prime() {
+            Ok(())
+        } else {
+            Err(Error::FactorizationOverNonPrimeModulus)
+        }
+    }
+
+    fn is_one(&self, value: &Polynomial<ModularField>) -> bool {
+        *value == self.one()
+    }
+
+    //g with g^p = value, for a value whose only non-zero coefficients sit at multiples of p.
+    //Every element of GF(p) is its own p-th power, so the coefficients stay the same.
+    fn pth_root(&self, value: &Polynomial<ModularField>, p: usize) -> Polynomial<ModularField> {
+        self.polynomial(value.coefficients().iter().step_by(p).cloned().collect())
+    }
+
+    //Monic square-free polynomials with multiplicities, so that value is the leading
+    //coefficient times the product of the factors raised to their multiplicities.
+    pub fn square_free_factorization(
+        &self,
+        value: &Polynomial<ModularField>,
+    ) -> Result<Vec<(Polynomial<ModularField>, usize)>, Error> {
+        self.check_prime_modulus()?;
+        if value.is_zero() {
+            return Err(Error::FactorizationOfZeroPolynomial);
+        }
+        let f = self.monic(value);
+        let mut result = vec![];
+        let mut c = self.gcd(&f, &self.derivative(&f));
+        let mut w = self.exact_quotient(&f, &c);
+        let mut i = 1;
+        while !self.is_one(&w) {
+            let y = self.gcd(&w, &c);
+            let factor = self.exact_quotient(&w, &y);
+            if !self.is_one(&factor) {
+                result.push((factor, i));
+            }
+            c = self.exact_quotient(&c, &y);
+            w = y;
+            i += 1;
+        }
+        //what is left has a zero derivative, so it is a p-th power
+        if !self.is_one(&c) {
+            let degree = c.degree().unwrap_or(0);
+            //over a prime field a non-constant p-th power has degree at least p, so p fits into
+            //usize; anything else means the modulus is not prime
+            let p = match self.underlying_field().modulus().to_usize() {
+                Some(p) if p <= degree => p,
+                _ => return Err(Error::FactorizationOverNonPrimeModulus),
+            };
+            for (factor, m) in self.square_free_factorization(&self.pth_root(&c, p))? {
+                result.push((factor, m * p));
+            }
+        }
+        Ok(result)
+    }
+
+    //For a monic square-free polynomial, pairs (g, d) where g is the product of all its
+    //irreducible factors of degree d
+    pub fn distinct_degree_factorization(
+        &self,
+        value: &Polynomial<ModularField>,
+    ) -> Result<Vec<(Polynomial<ModularField>, usize)>, Error> {
+        self.check_prime_modulus()?;
+        let p = self.underlying_field().modulus().clone();
+        let x = self.monomial(self.underlying_field().one(), 1);
+        let mut rest = self.monic(value);
+        let mut h = self.division_algorithm(&x, &rest)?.remainder;
+        let mut result = vec![];
+        let mut d = 1;
+        while rest.degree().unwrap_or(0) >= 2 * d {
+            //h = x^(p^d) mod rest, and x^(p^d) - x is the product of all monic irreducibles
+            //of degree dividing d
+            h = self.pow_mod(&h, &p, &rest)?;
+            let g = self.gcd(&rest, &self.add(&h, &self.neg(&x)));
+            if !self.is_one(&g) {
+                rest = self.exact_quotient(&rest, &g);
+                h = self.division_algorithm(&h, &rest)?.remainder;
+                result.push((g, d));
+            }
+            d += 1;
+        }
+        if let Some(degree) = rest.degree() {
+            if degree > 0 {
+                result.push((rest, degree));
+            }
+        }
+        Ok(result)
+    }
+
+    //Cantor-Zassenhaus splitting of a monic square-free polynomial whose irreducible factors
+    //all have the given degree. A degree that does not divide the degree of value is rejected
+    //up front; a polynomial that has other factors anyway is reported once the splitting
+    //attempts run out.
+    pub fn equal_degree_factorization(
+        &self,
+        value: &Polynomial<ModularField>,
+        degree: usize,
+    ) -> Result<Vec<Polynomial<ModularField>>, Error> {
+        self.check_prime_modulus()?;
+        let n = value.degree().unwrap_or(0);
+        if degree == 0 || n % degree != 0 {
+            return Err(Error::InvalidFactorDegree(n, degree));
+        }
+        let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+        let mut result = vec![];
+        self.split_equal_degree(&self.monic(value), degree, &mut rng, &mut result)?;
+        Ok(result)
+    }
+
+    fn split_equal_degree(
+        &self,
+        f: &Polynomial<ModularField>,
+        degree: usize,
+        rng: &mut XorShift,
+        result: &mut Vec<Polynomial<ModularField>>,
+    ) -> Result<(), Error> {
+        let n = f.degree().unwrap_or(0);
+        if n == 0 {
+            return Ok(());
+        }
+        if n <= degree {
+            result.push(f.clone());
+            return Ok(());
+        }
+        let field = self.underlying_field();
+        let p = field.modulus().clone();
+        let two = BigUint::from(2u64);
+        for _ in 0..MAX_SPLITTING_ATTEMPTS {
+            let a = self.polynomial((0..n).map(|_| rng.below(&p)).collect());
+            if a.degree().unwrap_or(0) == 0 {
+                continue;
+            }
+            let candidate = if p == two {
+                //trace a + a^2 + ... + a^(2^(degree - 1)) splits off about half the factors
+                let mut power = a.clone();
+                let mut trace = a.clone();
+                for _ in 1..degree {
+                    power = self.pow_mod(&power, &two, f)?;
+                    trace = self.add(&trace, &power);
+                }
+                trace
+            } else {
+                //a^((p^degree - 1) / 2) is 1 or -1 modulo each factor not dividing a
+                let order = (0..degree).fold(BigUint::from(1u64), |acc, _| acc * &p);
+                let exponent = (order - BigUint::from(1u64)) / &two;
+                let power = self.pow_mod(&a, &exponent, f)?;
+                self.add(&power, &self.neg(&self.one()))
+            };
+            let g = self.gcd(f, &candidate);
+            let g_degree = g.degree().unwrap_or(0);
+            if g_degree > 0 && g_degree < n {
+                let other = self.exact_quotient(f, &g);
+                self.split_equal_degree(&g, degree, rng, result)?;
+                return self.split_equal_degree(&other, degree, rng, result);
+            }
+        }
+        Err(Error::SplittingAttemptsExhausted)
+    }
+
+    //Monic irreducible factors with multiplicities, ordered by degree and then by
+    //coefficients. The leading coefficient of value is dropped.
+    pub fn factor(
+        &self,
+        value: &Polynomial<ModularField>,
+    ) -> Result<Vec<(Polynomial<ModularField>, usize)>, Error> {
+        self.check_prime_modulus()?;
+        let mut result = vec![];
+        for (square_free, multiplicity) in self.square_free_factorization(value)? {
+            for (product, degree) in self.distinct_degree_factorization(&square_free)? {
+                for factor in self.equal_degree_factorization(&product, degree)? {
+                    result.push((factor, multiplicity));
+                }
+            }
+        }
+        result.sort_by(|(a, _), (b, _)| {
+            a.degree().cmp(&b.degree()).then_with(|| {
+                a.coefficients()
+                    .iter()
+                    .rev()
+                    .cmp(b.coefficients().iter().rev())
+            })
+        });
+        Ok(result)
+    }
+}
+
+//BinaryRing elements are polynomials over GF(2), so they are factored as polynomials over
+//ModularField(2) and converted back
+impl<T: Unsigned> BinaryRing<T> {
+    fn as_gf2_polynomial<'f>(
+        &self,
+        ring: &PolynomialRing<'f, ModularField>,
+        value: &[T],
+    ) -> Polynomial<ModularField> {
+        let degree = BinaryRing::slice_degree(value);
+        ring.polynomial(
+            (0..=degree)
+                .map(|i| BigUint::from(self.bit_at(value, i as usize) as u64))
+                .collect(),
+        )
+    }
+
+    fn binary_of(&self, value: &Polynomial<ModularField>) -> Vec<T> {
+        let mut result = vec![T::ZERO; value.coefficients().len() / T::BITS as usize + 1];
+        for (i, c) in value.coefficients().iter().enumerate() {
+            if *c != BigUint::from(0u64) {
+                result[i / T::BITS as usize] |= T::ONE << (i % T::BITS as usize) as u32;
+            }
+        }
+        BinaryRing::clean_up(&mut result);
+        result
+    }
+
+    fn map_factors<F>(&self, value: &[T], factorization: F) -> Result<Vec<(Vec<T>, usize)>, Error>
+    where
+        F: Fn(
+            &PolynomialRing<ModularField>,
+            &Polynomial<ModularField>,
+        ) -> Result<Vec<(Polynomial<ModularField>, usize)>, Error>,
+    {
+        let field = ModularField::new(BigUint::from(2u64));
+        let ring = PolynomialRing::new(&field);
+        let factors = factorization(&ring, &self.as_gf2_polynomial(&ring, value))?;
+        Ok(factors
+            .iter()
+            .map(|(factor, m)| (self.binary_of(factor), *m))
+            .collect())
+    }
+
+    pub fn square_free_factorization(&self, value: &[T]) -> Result<Vec<(Vec<T>, usize)>, Error> {
+        self.map_factors(value, |ring, f| ring.square_free_factorization(f))
+    }
+
+    pub fn distinct_degree_factorization(
+        &self,
+        value: &[T],
+    ) -> Result<Vec<(Vec<T>, usize)>, Error> {
+        self.map_factors(value, |ring, f| ring.distinct_degree_factorization(f))
+    }
+
+    pub fn equal_degree_factorization(
+        &self,
+        value: &[T],
+        degree: usize,
+    ) -> Result<Vec<Vec<T>>, Error> {
+        let factors = self.map_factors(value, |ring, f| {
+            Ok(ring
+                .equal_degree_factorization(f, degree)?
+                .into_iter()
+                .map(|factor| (factor, 1))
+                .collect())
+        })?;
+        Ok(factors.into_iter().map(|(factor, _)| factor).collect())
+    }
+
+    //irreducible factors with multiplicities, ordered by degree
+    pub fn factor(&self, value: &[T]) -> Result<Vec<(Vec<T>, usize)>, Error> {
+        self.map_factors(value, |ring, f| ring.factor(f))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn poly<'a>(
+        ring: &PolynomialRing<'a, ModularField>,
+        coeffs: &[u64],
+    ) -> Polynomial<ModularField> {
+        ring.polynomial(coeffs.iter().map(|&c| BigUint::from(c)).collect())
+    }
+
+    fn expand<'a>(
+        ring: &PolynomialRing<'a, ModularField>,
+        factors: &[(Polynomial<ModularField>, usize)],
+    ) -> Polynomial<ModularField> {
+        factors.iter().fold(ring.one(), |acc, (f, m)| {
+            (0..*m).fold(acc, |acc, _| ring.mul(&acc, f))
+        })
+    }
+
+    #[test]
+    fn test_factor_mod_5() {
+        let field = ModularField::new(BigUint::from(5u64));
+        let ring = PolynomialRing::new(&field);
+        //(x + 1)^2 (x^2 + 2) (x + 3)^5, where x^2 + 2 is irreducible since -2 = 3 is not a square
+        let a = poly(&ring, &[1, 1]);
+        let b = poly(&ring, &[2, 0, 1]);
+        let c = poly(&ring, &[3, 1]);
+        let value = expand(&ring, &[(a.clone(), 2), (b.clone(), 1), (c.clone(), 5)]);
+        let value = ring.scale(&value, &BigUint::from(3u64));
+        assert_eq!(ring.factor(&value).unwrap(), vec![(a, 2), (c, 5), (b, 1)]);
+        assert!(matches!(
+            ring.factor(&ring.zero()),
+            Err(Error::FactorizationOfZeroPolynomial)
+        ));
+        assert!(ring.factor(&ring.one()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_distinct_and_equal_degree() {
+        let field = ModularField::new(BigUint::from(3u64));
+        let ring = PolynomialRing::new(&field);
+        //x^9 - x is the product of all monic irreducibles of degree 1 and 2 over GF(3)
+        let value = ring.add(
+            &ring.monomial(BigUint::from(1u64), 9),
+            &poly(&ring, &[0, 2]),
+        );
+        let ddf = ring.distinct_degree_factorization(&value).unwrap();
+        assert_eq!(ddf.len(), 2);
+        assert_eq!(ddf[0], (poly(&ring, &[0, 2, 0, 1]), 1));
+        assert_eq!(ddf[1].1, 2);
+        let quadratics = ring.equal_degree_factorization(&ddf[1].0, 2).unwrap();
+        assert_eq!(quadratics.len(), 3);
+        assert!(quadratics.iter().all(|q| q.degree() == Some(2)));
+    }
+
+    #[test]
+    fn test_equal_degree_bad_degree() {
+        let field = ModularField::new(BigUint::from(5u64));
+        let ring = PolynomialRing::new(&field);
+        //x^2 + 2 is irreducible over GF(5), so it has no factors of degree 1
+        let value = poly(&ring, &[2, 0, 1]);
+        assert!(matches!(
+            ring.equal_degree_factorization(&value, 0),
+            Err(Error::InvalidFactorDegree(2, 0))
+        ));
+        assert!(matches!(
+            ring.equal_degree_factorization(&value, 1),
+            Err(Error::SplittingAttemptsExhausted)
+        ));
+        let cubic = poly(&ring, &[1, 1, 0, 1]);
+        assert!(matches!(
+            ring.equal_degree_factorization(&cubic, 2),
+            Err(Error::InvalidFactorDegree(3, 2))
+        ));
+        assert_eq!(
+            ring.equal_degree_factorization(&value, 2).unwrap(),
+            vec![value]
+        );
+        let binary = BinaryRing::<u8>::new();
+        assert!(binary.equal_degree_factorization(&[0b111], 0).is_err());
+        assert!(matches!(
+            binary.equal_degree_factorization(&[0b111], 1),
+            Err(Error::SplittingAttemptsExhausted)
+        ));
+    }
+
+    #[test]
+    fn test_composite_modulus() {
+        for &modulus in [6u64, 9].iter() {
+            let field = ModularField::new(BigUint::from(modulus));
+            let ring = PolynomialRing::new(&field);
+            //x^2 + 3x + 2 = (x + 1) (x + 2), but Z/6 and Z/9 are not fields
+            let value = poly(&ring, &[2, 3, 1]);
+            let non_prime = |result: Result<_, Error>| {
+                matches!(result, Err(Error::FactorizationOverNonPrimeModulus))
+            };
+            assert!(non_prime(ring.factor(&value).map(|_| ())));
+            assert!(non_prime(
+                ring.square_free_factorization(&value).map(|_| ())
+            ));
+            assert!(non_prime(
+                ring.distinct_degree_factorization(&value).map(|_| ())
+            ));
+            assert!(non_prime(
+                ring.equal_degree_factorization(&value, 1).map(|_| ())
+            ));
+        }
+    }
+
+    #[test]
+    fn test_factor_binary() {
+        let ring = BinaryRing::<u8>::new();
+        //x^8 + x over GF(2) is x (x + 1) (x^3 + x + 1) (x^3 + x^2 + 1)
+        let factors = ring.factor(&[0b10, 0b1]).unwrap();
+        assert_eq!(
+            factors,
+            vec![
+                (vec![0b10], 1),
+                (vec![0b11], 1),
+                (vec![0b1011], 1),
+                (vec![0b1101], 1)
+            ]
+        );
+        //(x^2 + x + 1)^3 = x^6 + x^5 + x^3 + x + 1 is not square free
+        let factors = ring.factor(&[0b1101011]).unwrap();
+        assert_eq!(factors, vec![(vec![0b111], 3)]);
+        assert_eq!(
+            ring.square_free_factorization(&[0b1101011]).unwrap(),
+            vec![(vec![0b111], 3)]
+        );
+        //x^64 + x^4 + x^3 + x + 1 spans several words
+        let value = vec![0b11011, 0, 0, 0, 0, 0, 0, 0, 1];
+        let factors = ring.factor(&value).unwrap();
+        let product = factors.iter().fold(ring.one(), |acc, (f, m)| {
+            (0..*m).fold(acc, |acc, _| ring.mul(&acc, f))
+        });
+        assert_eq!(product, value);
+    }
+
+    proptest! {
+        #[test]
+        fn test_factor_random(coeffs in proptest::collection::vec(0u64..7, 1..14)) {
+            let field = ModularField::new(BigUint::from(7u64));
+            let ring = PolynomialRing::new(&field);
+            let value = poly(&ring, &coeffs);
+            if !value.is_zero() {
+                let factors = ring.factor(&value).unwrap();
+                assert_eq!(expand(&ring, &factors), ring.monic(&value));
+                for (factor, _) in factors.iter() {
+                    let degree = factor.degree().unwrap();
+                    let ddf = ring.distinct_degree_factorization(factor).unwrap();
+                    assert_eq!(ddf, vec![(factor.clone(), degree)]);
+                }
+            }
+        }
+    }
+}
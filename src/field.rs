@@ -20,22 +20,37 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
  * DEALINGS IN THE SOFTWARE.
  */
+use crate::arith::is_probable_prime;
 use crate::error::Error;
+use crate::pivoting::{Pivoting, DEFAULT_TOLERANCE};
 use crate::Field;
 use crate::Ring;
 use num::BigUint;
 use num::ToBigInt;
-use num_bigint::BigInt;
+use num_bigint::{BigInt, Sign};
 use num_complex::Complex;
 use num_integer::Integer;
+use std::cmp::Ordering;
+use std::fmt::Display;
 #[derive(Clone, PartialEq, Debug)]
 pub struct ModularField {
     modulus: BigUint,
+    //decided once here, since the polynomial multiplication asks on every call
+    prime: bool,
 }
 
 impl ModularField {
     pub fn new(modulus: BigUint) -> ModularField {
-        ModularField { modulus }
+        let prime = is_probable_prime(&modulus);
+        ModularField { modulus, prime }
+    }
+
+    pub fn modulus(&self) -> &BigUint {
+        &self.modulus
+    }
+
+    pub fn is_prime(&self) -> bool {
+        self.prime
     }
 }
 
@@ -54,12 +69,42 @@ impl Field for ModularField {
         }
         Ok(inv.to_biguint().unwrap())
     }
+
+    //Only for a prime modulus p, since over a composite modulus a root found this way need not
+    //be principal and the transform built on it would be wrong. A root of order 2^k exists
+    //when 2^k divides p - 1, and g^((p - 1) / 2^k) is one exactly when g is a quadratic
+    //non-residue.
+    fn root_of_unity(&self, order: usize) -> Option<BigUint> {
+        if !self.prime {
+            return None;
+        }
+        let one = self.one();
+        let p_minus_one = &self.modulus - &one;
+        let order_big = BigUint::from(order as u64);
+        if !order.is_power_of_two() || (&p_minus_one % &order_big) != self.zero() {
+            return None;
+        }
+        let exponent = &p_minus_one / &order_big;
+        if order == 1 {
+            return Some(one);
+        }
+        let half = BigUint::from((order / 2) as u64);
+        //half of all candidates are non-residues
+        for g in 2u64..1000 {
+            let root = BigUint::from(g).modpow(&exponent, &self.modulus);
+            if root.modpow(&half, &self.modulus) != one {
+                return Some(root);
+            }
+        }
+        None
+    }
 }
 
 impl Ring for ModularField {
     type RingMember = BigUint;
     fn neg(&self, x: &BigUint) -> BigUint {
-        &self.modulus - x
+        //reduced again so that the negation of zero is zero and not the modulus
+        (&self.modulus - x) % &self.modulus
     }
     fn mul(&self, x: &BigUint, y: &BigUint) -> BigUint {
         (x * y) % &self.modulus
@@ -106,6 +151,16 @@ impl Field for F64Field {
             Ok(self.one() / x)
         }
     }
+
+    fn magnitude(&self, x: &f64) -> f64 {
+        x.abs()
+    }
+
+    fn pivoting(&self) -> Pivoting {
+        Pivoting::Partial {
+            tolerance: DEFAULT_TOLERANCE,
+        }
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Debug)]
@@ -138,4 +193,260 @@ impl Field for ComplexField {
             Ok(self.one() / x)
         }
     }
+
+    fn magnitude(&self, x: &Complex<f64>) -> f64 {
+        x.norm()
+    }
+
+    fn pivoting(&self) -> Pivoting {
+        Pivoting::Partial {
+            tolerance: DEFAULT_TOLERANCE,
+        }
+    }
+}
+
+//Exact rational number, always kept with a positive denominator and the numerator and
+//denominator coprime, so the derived equality is equality of values
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Rational {
+    numerator: BigInt,
+    denominator: BigInt,
+}
+
+impl Rational {
+    pub fn new(numerator: BigInt, denominator: BigInt) -> Result<Rational, Error> {
+        if denominator == BigInt::from(0) {
+            return Err(Error::DivisionByZero);
+        }
+        let mut gcd = numerator.gcd(&denominator);
+        if denominator < BigInt::from(0) {
+            gcd = -gcd;
+        }
+        Ok(Rational {
+            numerator: numerator / &gcd,
+            denominator: denominator / &gcd,
+        })
+    }
+
+    pub fn from_integer(value: BigInt) -> Rational {
+        Rational {
+            numerator: value,
+            denominator: BigInt::from(1),
+        }
+    }
+
+    pub fn numerator(&self) -> &BigInt {
+        &self.numerator
+    }
+
+    pub fn denominator(&self) -> &BigInt {
+        &self.denominator
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == BigInt::from(1)
+    }
+
+    pub fn floor(&self) -> BigInt {
+        self.numerator.div_floor(&self.denominator)
+    }
+
+    //nearest integer, halves are rounded up
+    pub fn round(&self) -> BigInt {
+        let two = BigInt::from(2);
+        (&two * &self.numerator + &self.denominator).div_floor(&(&two * &self.denominator))
+    }
+
+    //Nearest f64, up to rounding. The quotient is first taken with 64 significant bits in
+    //integer arithmetic, so huge numerators and denominators don't overflow on the way.
+    pub fn to_f64(&self) -> f64 {
+        let (sign, numerator) = self.numerator.to_bytes_le();
+        if sign == Sign::NoSign {
+            return 0.0;
+        }
+        let numerator = BigUint::from_bytes_le(&numerator);
+        let denominator = BigUint::from_bytes_le(&self.denominator.to_bytes_le().1);
+        let exponent = numerator.bits() as i64 - denominator.bits() as i64 - 64;
+        //numerator / denominator = quotient * 2^exponent with 2^63 <= quotient < 2^65
+        let quotient = if exponent < 0 {
+            (numerator << (-exponent) as usize) / denominator
+        } else {
+            numerator / (denominator << exponent as usize)
+        };
+        let mut value = quotient
+            .to_bytes_le()
+            .iter()
+            .rev()
+            .fold(0f64, |acc, &byte| acc * 256.0 + byte as f64);
+        let mut exponent = exponent;
+        while exponent > 1000 {
+            value *= 2f64.powi(1000);
+            exponent -= 1000;
+        }
+        while exponent < -1000 {
+            value *= 2f64.powi(-1000);
+            exponent += 1000;
+        }
+        value *= 2f64.powi(exponent as i32);
+        if sign == Sign::Minus {
+            -value
+        } else {
+            value
+        }
+    }
+}
+
+impl From<BigInt> for Rational {
+    fn from(value: BigInt) -> Rational {
+        Rational::from_integer(value)
+    }
+}
+
+impl From<&BigInt> for Rational {
+    fn from(value: &BigInt) -> Rational {
+        Rational::from_integer(value.clone())
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Rational {
+        Rational::from_integer(BigInt::from(value))
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Rational) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Rational) -> Ordering {
+        (&self.numerator * &other.denominator).cmp(&(&other.numerator * &self.denominator))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RationalField;
+
+impl Ring for RationalField {
+    type RingMember = Rational;
+
+    fn add(&self, x: &Rational, y: &Rational) -> Rational {
+        Rational::new(
+            &x.numerator * &y.denominator + &y.numerator * &x.denominator,
+            &x.denominator * &y.denominator,
+        )
+        .unwrap()
+    }
+    fn mul(&self, x: &Rational, y: &Rational) -> Rational {
+        Rational::new(&x.numerator * &y.numerator, &x.denominator * &y.denominator).unwrap()
+    }
+    fn neg(&self, x: &Rational) -> Rational {
+        Rational {
+            numerator: -&x.numerator,
+            denominator: x.denominator.clone(),
+        }
+    }
+    fn zero(&self) -> Rational {
+        Rational::from_integer(BigInt::from(0))
+    }
+    fn one(&self) -> Rational {
+        Rational::from_integer(BigInt::from(1))
+    }
+}
+
+impl Field for RationalField {
+    fn inv(&self, x: &Rational) -> Result<Rational, Error> {
+        Rational::new(x.denominator.clone(), x.numerator.clone())
+    }
+
+    fn magnitude(&self, x: &Rational) -> f64 {
+        x.to_f64().abs()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Matrix;
+
+    fn q(numerator: i64, denominator: i64) -> Rational {
+        Rational::new(BigInt::from(numerator), BigInt::from(denominator)).unwrap()
+    }
+
+    #[test]
+    fn test_modular_neg_is_reduced() {
+        let ring = ModularField::new(BigUint::from(7u64));
+        assert_eq!(ring.neg(&ring.zero()), ring.zero());
+        assert_eq!(ring.neg(&BigUint::from(3u64)), BigUint::from(4u64));
+    }
+
+    #[test]
+    fn test_rational_normalisation() {
+        assert_eq!(q(6, -4), q(-3, 2));
+        assert_eq!(q(6, -4).denominator(), &BigInt::from(2));
+        assert_eq!(q(0, 5), RationalField.zero());
+        assert!(Rational::new(BigInt::from(1), BigInt::from(0)).is_err());
+        assert!(RationalField.inv(&q(0, 3)).is_err());
+        assert_eq!(q(-7, 2).floor(), BigInt::from(-4));
+        assert_eq!(q(-7, 2).round(), BigInt::from(-3));
+        assert_eq!(Rational::from(BigInt::from(4)), q(8, 2));
+        assert!(q(1, 3) < q(1, 2) && q(-1, 2) < q(-1, 3));
+        assert_eq!(q(-3, 2).to_string(), "-3/2");
+    }
+
+    #[test]
+    fn test_rational_to_f64() {
+        assert_eq!(q(1, 3).to_f64(), 1.0 / 3.0);
+        assert_eq!(q(-22, 7).to_f64(), -22.0 / 7.0);
+        assert_eq!(Rational::from(0).to_f64(), 0.0);
+        let huge = BigInt::from(3) << 1400;
+        let ratio = Rational::new(&huge * BigInt::from(5), huge * BigInt::from(4)).unwrap();
+        assert_eq!(ratio.to_f64(), 1.25);
+        let tiny = Rational::new(BigInt::from(1), BigInt::from(1) << 1060).unwrap();
+        assert_eq!(tiny.to_f64(), 2f64.powi(-1000) * 2f64.powi(-60));
+    }
+
+    #[test]
+    fn test_exact_inverse() {
+        let ring = RationalField;
+        let mat = Matrix::new(
+            &ring,
+            vec![
+                vec![q(2, 1), q(1, 1), q(1, 1)],
+                vec![q(1, 1), q(3, 1), q(2, 1)],
+                vec![q(1, 1), q(0, 1), q(0, 1)],
+            ],
+        );
+        let inverse = mat.inverse().unwrap();
+        assert_eq!(inverse[(0, 2)], q(1, 1));
+        assert_eq!(inverse[(1, 0)], q(-2, 1));
+        assert_eq!(mat.checked_mul(&inverse).unwrap(), Matrix::one(&ring, 3));
+        assert_eq!(mat.determinant().unwrap(), q(-1, 1));
+        let rhs = Matrix::new(&ring, vec![vec![q(1, 1)], vec![q(0, 1)], vec![q(1, 2)]]);
+        let solution = mat.solve(&rhs).unwrap();
+        assert_eq!(solution.particular, inverse.checked_mul(&rhs).unwrap());
+        let hilbert = Matrix::new(
+            &ring,
+            (1..=4)
+                .map(|i| (1..=4).map(|j| q(1, i + j - 1)).collect())
+                .collect(),
+        );
+        assert_eq!(hilbert.determinant().unwrap(), q(1, 6048000));
+        assert_eq!(
+            hilbert.inverse().unwrap().checked_mul(&hilbert).unwrap(),
+            Matrix::one(&ring, 4)
+        );
+    }
 }
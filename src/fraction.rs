@@ -0,0 +1,193 @@
+/*
+ * Copyright  2020 Debasish Ray Chawdhuri
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do
+ * so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+ * EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+ * MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT
+ * HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+ * WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+ * DEALINGS IN THE SOFTWARE.
+ */
+use crate::error::Error;
+use crate::euclidian_domain::EuclidianDomain;
+use crate::{Field, Ring};
+
+//numerator / denominator, reduced by FractionField so that equal fractions compare equal
+#[derive(Clone, PartialEq, Debug)]
+pub struct Fraction<T> {
+    numerator: T,
+    denominator: T,
+}
+
+impl<T> Fraction<T> {
+    pub fn numerator(&self) -> &T {
+        &self.numerator
+    }
+
+    pub fn denominator(&self) -> &T {
+        &self.denominator
+    }
+}
+
+//Field of fractions of a Euclidian domain. Fractions are kept with coprime numerator and
+//denominator, and the denominator normalised with EuclidianDomain::normalization_unit.
+#[derive(Clone, PartialEq, Debug)]
+pub struct FractionField<R: EuclidianDomain> {
+    ring: R,
+}
+
+impl<R: EuclidianDomain> FractionField<R> {
+    pub fn new(ring: R) -> Self {
+        FractionField { ring }
+    }
+
+    pub fn ring(&self) -> &R {
+        &self.ring
+    }
+
+    pub fn fraction(
+        &self,
+        numerator: &R::RingMember,
+        denominator: &R::RingMember,
+    ) -> Result<Fraction<R::RingMember>, Error> {
+        let ring = &self.ring;
+        //adding zero brings both into the canonical form of the ring
+        let numerator = ring.add(numerator, &ring.zero());
+        let denominator = ring.add(denominator, &ring.zero());
+        if denominator == ring.zero() {
+            return Err(Error::DivisionByZero);
+        }
+        if numerator == ring.zero() {
+            return Ok(self.zero());
+        }
+        let gcd = ring.extended_euclid(&numerator, &denominator)?.gcd;
+        let numerator = ring.division_algorithm(&numerator, &gcd)?.quotient;
+        let denominator = ring.division_algorithm(&denominator, &gcd)?.quotient;
+        let unit = ring.normalization_unit(&denominator);
+        Ok(Fraction {
+            numerator: ring.mul(&numerator, &unit),
+            denominator: ring.mul(&denominator, &unit),
+        })
+    }
+
+    pub fn from_ring_element(&self, value: &R::RingMember) -> Fraction<R::RingMember> {
+        Fraction {
+            numerator: self.ring.add(value, &self.ring.zero()),
+            denominator: self.ring.one(),
+        }
+    }
+}
+
+impl<R: EuclidianDomain> Ring for FractionField<R> {
+    type RingMember = Fraction<R::RingMember>;
+
+    fn add(&self, lhs: &Self::RingMember, rhs: &Self::RingMember) -> Self::RingMember {
+        let ring = &self.ring;
+        let numerator = ring.add(
+            &ring.mul(&lhs.numerator, &rhs.denominator),
+            &ring.mul(&rhs.numerator, &lhs.denominator),
+        );
+        let denominator = ring.mul(&lhs.denominator, &rhs.denominator);
+        //the product of two non-zero denominators is never zero
+        self.fraction(&numerator, &denominator).unwrap()
+    }
+
+    fn mul(&self, lhs: &Self::RingMember, rhs: &Self::RingMember) -> Self::RingMember {
+        let ring = &self.ring;
+        let numerator = ring.mul(&lhs.numerator, &rhs.numerator);
+        let denominator = ring.mul(&lhs.denominator, &rhs.denominator);
+        self.fraction(&numerator, &denominator).unwrap()
+    }
+
+    fn neg(&self, lhs: &Self::RingMember) -> Self::RingMember {
+        Fraction {
+            numerator: self.ring.neg(&lhs.numerator),
+            denominator: lhs.denominator.clone(),
+        }
+    }
+
+    fn zero(&self) -> Self::RingMember {
+        Fraction {
+            numerator: self.ring.zero(),
+            denominator: self.ring.one(),
+        }
+    }
+
+    fn one(&self) -> Self::RingMember {
+        Fraction {
+            numerator: self.ring.one(),
+            denominator: self.ring.one(),
+        }
+    }
+}
+
+impl<R: EuclidianDomain> Field for FractionField<R> {
+    fn inv(&self, value: &Self::RingMember) -> Result<Self::RingMember, Error> {
+        self.fraction(&value.denominator, &value.numerator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binary::BinaryRing;
+    use crate::field::{Rational, RationalField};
+    use crate::int_ring::BigIntRing;
+    use crate::Matrix;
+    use num::BigInt;
+
+    #[test]
+    fn test_rationals() {
+        let field = FractionField::new(BigIntRing);
+        let q = |n: i64, d: i64| field.fraction(&BigInt::from(n), &BigInt::from(d)).unwrap();
+        assert_eq!(q(6, -4), q(-3, 2));
+        assert_eq!(q(6, -4).denominator(), &BigInt::from(2));
+        assert_eq!(field.add(&q(1, 2), &q(1, 3)), q(5, 6));
+        assert_eq!(field.mul(&q(2, 3), &q(-9, 4)), q(-3, 2));
+        assert_eq!(field.add(&q(1, 2), &field.neg(&q(1, 2))), field.zero());
+        assert_eq!(field.inv(&q(-2, 7)).unwrap(), q(-7, 2));
+        assert!(field.inv(&field.zero()).is_err());
+        assert_eq!(field.from_ring_element(&BigInt::from(3)), q(6, 2));
+
+        //same results as the dedicated rational field
+        let rationals = RationalField;
+        let r = |n: i64, d: i64| Rational::new(BigInt::from(n), BigInt::from(d)).unwrap();
+        let sum = rationals.add(&r(1, 2), &r(1, 3));
+        let fraction = field.add(&q(1, 2), &q(1, 3));
+        assert_eq!(sum.numerator(), fraction.numerator());
+        assert_eq!(sum.denominator(), fraction.denominator());
+    }
+
+    #[test]
+    fn test_rational_functions() {
+        let field = FractionField::new(BinaryRing::<u64>::new());
+        let f = |n: u64, d: u64| field.fraction(&vec![n], &vec![d]).unwrap();
+        //(x + 1) / (x^2 + 1) = 1 / (x + 1) over GF(2)
+        assert_eq!(f(0b11, 0b101), f(1, 0b11));
+        //1 / x + 1 / (x + 1) = 1 / (x^2 + x)
+        assert_eq!(field.add(&f(1, 0b10), &f(1, 0b11)), f(1, 0b110));
+        assert_eq!(field.add(&f(0b111, 0b10), &f(0b111, 0b10)), field.zero());
+
+        let mat = Matrix::new(
+            &field,
+            vec![vec![f(0b10, 1), f(1, 1)], vec![f(1, 1), f(0b11, 1)]],
+        );
+        let inverse = mat.inverse().unwrap();
+        assert_eq!(mat.checked_mul(&inverse).unwrap(), Matrix::one(&field, 2));
+        //det = x^2 + x + 1
+        assert_eq!(mat.determinant().unwrap(), f(0b111, 1));
+        assert_eq!(inverse[(0, 1)], f(1, 0b111));
+    }
+}
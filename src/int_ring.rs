@@ -24,12 +24,12 @@
 use crate::error::Error;
 use crate::euclidian_domain::DivisionAlgorithmResult;
 use crate::euclidian_domain::EuclidianDomain;
-use crate::Field;
 use crate::Ring;
 
-use num_bigint::BigInt;
+pub use crate::polynomial::{Polynomial, PolynomialRing};
+
+use num_bigint::{BigInt, Sign};
 use proptest::prelude::*;
-use std::fmt::Debug;
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct I32Ring;
@@ -96,6 +96,14 @@ impl EuclidianDomain for BigIntRing {
             Err(Error::DivisionByZero)
         }
     }
+
+    fn normalization_unit(&self, value: &Self::RingMember) -> Self::RingMember {
+        if value.sign() == Sign::Minus {
+            BigInt::from(-1)
+        } else {
+            self.one()
+        }
+    }
 }
 impl Ring for I64Ring {
     type RingMember = i64;
@@ -117,50 +125,6 @@ impl Ring for I64Ring {
     }
 }
 
-#[derive(Clone, PartialEq, Debug)]
-pub struct Polynomial<T>
-where
-    T: Field,
-{
-    coeffs: Vec<T::RingMember>,
-}
-
-#[derive(Clone, PartialEq, Debug)]
-pub struct PolynomialRing<'a, T: Field> {
-    underlying_field: &'a T,
-}
-
-impl<'a, T: Field + PartialEq + Clone> Ring for PolynomialRing<'a, T> {
-    type RingMember = Polynomial<T>;
-
-    fn add(&self, lhs: &Self::RingMember, rhs: &Self::RingMember) -> Self::RingMember {
-        let sum_coeffs: Vec<T::RingMember> = lhs
-            .coeffs
-            .iter()
-            .zip(rhs.coeffs.iter())
-            .map(|(l, r)| self.underlying_field.add(l, r))
-            .collect();
-
-        Polynomial { coeffs: sum_coeffs }
-    }
-    #[allow(unused)]
-    fn mul(&self, lhs: &Self::RingMember, rhs: &Self::RingMember) -> Self::RingMember {
-        todo!()
-    }
-    #[allow(unused)]
-    fn neg(&self, lhs: &Self::RingMember) -> Self::RingMember {
-        todo!()
-    }
-
-    fn zero(&self) -> Self::RingMember {
-        todo!()
-    }
-
-    fn one(&self) -> Self::RingMember {
-        todo!()
-    }
-}
-
 proptest! {
     #[test]
     fn test_euclid(a:u32, b:u32){
@@ -175,4 +139,18 @@ proptest! {
             assert_eq!(eres.x * a+eres.y*b,gcd);
         }
     }
+
+    #[test]
+    fn test_division_algorithm(a:i32, b:i32){
+        if b!=0{
+            let a = BigInt::from(a);
+            let b = BigInt::from(b);
+            let ring = BigIntRing;
+            let res = ring.division_algorithm(&a,&b).unwrap();
+            //truncated division, the remainder takes the sign of the value
+            assert!(res.remainder == ring.zero() || res.remainder.sign() == a.sign());
+            assert!(&res.remainder * ring.normalization_unit(&res.remainder) < &b * ring.normalization_unit(&b));
+            assert_eq!(res.quotient * b + res.remainder, a);
+        }
+    }
 }
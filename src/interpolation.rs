@@ -0,0 +1,311 @@
+/*
+ * Copyright  2020 Debasish Ray Chawdhuri
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do
+ * so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+ * EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+ * MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT
+ * HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+ * WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+ * DEALINGS IN THE SOFTWARE.
+ */
+use crate::error::Error;
+use crate::euclidian_domain::EuclidianDomain;
+use crate::polynomial::{Polynomial, PolynomialRing};
+use crate::{Field, Ring};
+
+//Products of the linear factors (x - x_i) arranged as a binary tree. Level 0 holds the
+//linear factors and node j of a level is the product of nodes 2j and 2j + 1 of the level
+//below (an odd node out is carried up unchanged), so the last level holds the product of
+//all of them.
+pub struct SubproductTree<T: Field> {
+    points: Vec<T::RingMember>,
+    levels: Vec<Vec<Polynomial<T>>>,
+}
+
+impl<T: Field> SubproductTree<T> {
+    pub fn points(&self) -> &[T::RingMember] {
+        &self.points
+    }
+
+    //prod (x - x_i) over all points
+    pub fn root(&self) -> &Polynomial<T> {
+        &self.levels[self.levels.len() - 1][0]
+    }
+}
+
+impl<'a, T: Field> PolynomialRing<'a, T> {
+    //x - point
+    fn linear_factor(&self, point: &T::RingMember) -> Polynomial<T> {
+        let field = self.underlying_field();
+        self.polynomial(vec![field.neg(point), field.one()])
+    }
+
+    fn check_distinct(&self, points: &[T::RingMember]) -> Result<(), Error> {
+        for (i, x) in points.iter().enumerate() {
+            if points[..i].contains(x) {
+                return Err(Error::RepeatedInterpolationNode);
+            }
+        }
+        Ok(())
+    }
+
+    //Unique polynomial of degree below points.len() through every (x, y), built from the
+    //Lagrange basis polynomials prod_{j != i} (x - x_j) / (x_i - x_j)
+    pub fn lagrange_interpolation(
+        &self,
+        points: &[(T::RingMember, T::RingMember)],
+    ) -> Result<Polynomial<T>, Error> {
+        let field = self.underlying_field();
+        let xs: Vec<T::RingMember> = points.iter().map(|(x, _)| x.clone()).collect();
+        self.check_distinct(&xs)?;
+        let master = xs
+            .iter()
+            .fold(self.one(), |acc, x| self.mul(&acc, &self.linear_factor(x)));
+        let mut result = self.zero();
+        for (i, (x, y)) in points.iter().enumerate() {
+            let basis = self
+                .division_algorithm(&master, &self.linear_factor(x))?
+                .quotient;
+            let denominator = xs
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .fold(field.one(), |acc, (_, other)| {
+                    field.mul(&acc, &field.add(x, &field.neg(other)))
+                });
+            let weight = field.mul(y, &field.inv(&denominator)?);
+            result = self.add(&result, &self.scale(&basis, &weight));
+        }
+        Ok(result)
+    }
+
+    //Same polynomial as lagrange_interpolation, computed from divided differences
+    pub fn newton_interpolation(
+        &self,
+        points: &[(T::RingMember, T::RingMember)],
+    ) -> Result<Polynomial<T>, Error> {
+        let field = self.underlying_field();
+        let xs: Vec<T::RingMember> = points.iter().map(|(x, _)| x.clone()).collect();
+        self.check_distinct(&xs)?;
+        let n = points.len();
+        //after step j, coeffs[i] holds the divided difference [y_(i-j), ..., y_i]
+        let mut coeffs: Vec<T::RingMember> = points.iter().map(|(_, y)| y.clone()).collect();
+        for j in 1..n {
+            for i in (j..n).rev() {
+                let numerator = field.add(&coeffs[i], &field.neg(&coeffs[i - 1]));
+                let denominator = field.add(&xs[i], &field.neg(&xs[i - j]));
+                coeffs[i] = field.mul(&numerator, &field.inv(&denominator)?);
+            }
+        }
+        let mut result = self.zero();
+        for (c, x) in coeffs.iter().zip(xs.iter()).rev() {
+            result = self.add(
+                &self.mul(&result, &self.linear_factor(x)),
+                &self.polynomial(vec![c.clone()]),
+            );
+        }
+        Ok(result)
+    }
+
+    pub fn subproduct_tree(&self, points: &[T::RingMember]) -> SubproductTree<T> {
+        let mut levels = vec![];
+        let mut level: Vec<Polynomial<T>> = points.iter().map(|x| self.linear_factor(x)).collect();
+        if level.is_empty() {
+            level.push(self.one());
+        }
+        while level.len() > 1 {
+            let next = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => self.mul(left, right),
+                    _ => pair[0].clone(),
+                })
+                .collect();
+            levels.push(level);
+            level = next;
+        }
+        levels.push(level);
+        SubproductTree {
+            points: points.to_vec(),
+            levels,
+        }
+    }
+
+    //p(x_i) for every point of the tree, by reducing p modulo the nodes from the root down
+    pub fn evaluate_on_tree(
+        &self,
+        tree: &SubproductTree<T>,
+        p: &Polynomial<T>,
+    ) -> Result<Vec<T::RingMember>, Error> {
+        let field = self.underlying_field();
+        let mut remainders = vec![self.division_algorithm(p, tree.root())?.remainder];
+        for level in tree.levels.iter().rev().skip(1) {
+            let mut next = Vec::with_capacity(level.len());
+            for (j, node) in level.iter().enumerate() {
+                next.push(self.division_algorithm(&remainders[j / 2], node)?.remainder);
+            }
+            remainders = next;
+        }
+        Ok(remainders
+            .iter()
+            .take(tree.points.len())
+            .map(|r| {
+                r.coefficients()
+                    .first()
+                    .cloned()
+                    .unwrap_or_else(|| field.zero())
+            })
+            .collect())
+    }
+
+    pub fn multipoint_evaluate(
+        &self,
+        p: &Polynomial<T>,
+        points: &[T::RingMember],
+    ) -> Result<Vec<T::RingMember>, Error> {
+        self.evaluate_on_tree(&self.subproduct_tree(points), p)
+    }
+
+    //Interpolation through the subproduct tree. With m = prod (x - x_i), the result is
+    //sum_i y_i / m'(x_i) * m / (x - x_i), and the sum is assembled bottom up along the tree.
+    pub fn fast_interpolation(
+        &self,
+        points: &[(T::RingMember, T::RingMember)],
+    ) -> Result<Polynomial<T>, Error> {
+        let field = self.underlying_field();
+        let xs: Vec<T::RingMember> = points.iter().map(|(x, _)| x.clone()).collect();
+        if points.is_empty() {
+            return Ok(self.zero());
+        }
+        let tree = self.subproduct_tree(&xs);
+        let derivative_values = self.evaluate_on_tree(&tree, &self.derivative(tree.root()))?;
+        let mut combined = Vec::with_capacity(points.len());
+        for ((_, y), d) in points.iter().zip(derivative_values.iter()) {
+            //m'(x_i) vanishes exactly when x_i is a repeated root of m
+            if *d == field.zero() {
+                return Err(Error::RepeatedInterpolationNode);
+            }
+            combined.push(self.polynomial(vec![field.mul(y, &field.inv(d)?)]));
+        }
+        for level in tree.levels.iter().take(tree.levels.len() - 1) {
+            combined = combined
+                .chunks(2)
+                .zip(level.chunks(2))
+                .map(|(values, nodes)| match (values, nodes) {
+                    ([left, right], [left_node, right_node]) => {
+                        self.add(&self.mul(left, right_node), &self.mul(right, left_node))
+                    }
+                    _ => values[0].clone(),
+                })
+                .collect();
+        }
+        Ok(combined.swap_remove(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binary::{BinaryField, Newable};
+    use crate::field::ModularField;
+    use num::BigUint;
+    use proptest::prelude::*;
+
+    fn n(x: u64) -> BigUint {
+        BigUint::from(x)
+    }
+
+    #[test]
+    fn test_shamir_secret_sharing() {
+        let field = ModularField::new(n(65537));
+        let ring = PolynomialRing::new(&field);
+        //secret 1234 with threshold 3
+        let p = ring.polynomial(vec![n(1234), n(166), n(94)]);
+        let shares: Vec<(BigUint, BigUint)> = (1..=5u64)
+            .map(|x| (n(x), ring.evaluate(&p, &n(x))))
+            .collect();
+        for subset in [&shares[0..3], &shares[2..5], &shares[1..4]] {
+            let lagrange = ring.lagrange_interpolation(subset).unwrap();
+            assert_eq!(lagrange, p);
+            assert_eq!(ring.newton_interpolation(subset).unwrap(), p);
+            assert_eq!(ring.fast_interpolation(subset).unwrap(), p);
+            assert_eq!(ring.evaluate(&lagrange, &n(0)), n(1234));
+        }
+        let repeated = [shares[0].clone(), shares[0].clone()];
+        assert!(matches!(
+            ring.lagrange_interpolation(&repeated),
+            Err(Error::RepeatedInterpolationNode)
+        ));
+        assert!(ring.newton_interpolation(&repeated).is_err());
+        assert!(ring.fast_interpolation(&repeated).is_err());
+    }
+
+    #[test]
+    fn test_reed_solomon_over_gf256() {
+        let field = BinaryField::<u8>::new();
+        let ring = PolynomialRing::new(&field);
+        let message = ring.polynomial(vec![0x48, 0x65, 0x6c, 0x6c, 0x6f]);
+        let points: Vec<u8> = (1..=9).collect();
+        let codeword = ring.multipoint_evaluate(&message, &points).unwrap();
+        let expected: Vec<u8> = points.iter().map(|x| ring.evaluate(&message, x)).collect();
+        assert_eq!(codeword, expected);
+        //any five symbols recover the message
+        let received: Vec<(u8, u8)> = points
+            .iter()
+            .cloned()
+            .zip(codeword.iter().cloned())
+            .skip(4)
+            .collect();
+        assert_eq!(ring.fast_interpolation(&received).unwrap(), message);
+        assert_eq!(ring.newton_interpolation(&received).unwrap(), message);
+    }
+
+    #[test]
+    fn test_subproduct_tree() {
+        let field = ModularField::new(n(101));
+        let ring = PolynomialRing::new(&field);
+        let points = vec![n(1), n(2), n(3)];
+        let tree = ring.subproduct_tree(&points);
+        //(x - 1)(x - 2)(x - 3) = x^3 - 6x^2 + 11x - 6
+        assert_eq!(
+            tree.root(),
+            &ring.polynomial(vec![n(95), n(11), n(95), n(1)])
+        );
+        assert_eq!(tree.points(), &points[..]);
+        assert_eq!(ring.subproduct_tree(&[]).root(), &ring.one());
+        assert!(ring
+            .multipoint_evaluate(&ring.one(), &[])
+            .unwrap()
+            .is_empty());
+    }
+
+    proptest! {
+        #[test]
+        fn test_multipoint_evaluation(coeffs in proptest::collection::vec(0u64..257, 0..20),
+                                      count in 1u64..40) {
+            let field = ModularField::new(n(257));
+            let ring = PolynomialRing::new(&field);
+            let p = ring.polynomial(coeffs.into_iter().map(n).collect());
+            let points: Vec<BigUint> = (0..count).map(|x| n(x * 3 + 1)).collect();
+            let values = ring.multipoint_evaluate(&p, &points).unwrap();
+            let naive: Vec<BigUint> = points.iter().map(|x| ring.evaluate(&p, x)).collect();
+            assert_eq!(&values, &naive);
+            if p.degree().map_or(true, |d| d < points.len()) {
+                let pairs: Vec<(BigUint, BigUint)> = points.into_iter().zip(values).collect();
+                assert_eq!(ring.fast_interpolation(&pairs).unwrap(), p);
+            }
+        }
+    }
+}
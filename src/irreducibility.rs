@@ -0,0 +1,307 @@
+/*
+ * Copyright  2020 Debasish Ray Chawdhuri
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do
+ * so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+ * EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+ * MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT
+ * HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+ * WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+ * DEALINGS IN THE SOFTWARE.
+ */
+use crate::arith::{is_probable_prime, SMALL_PRIMES};
+use crate::binary::BinaryRing;
+use crate::Ring;
+use funty::Unsigned;
+use num_bigint::BigUint;
+use num_integer::Integer;
+
+//Some non-trivial divisor of a composite n that has no small prime factors, by Pollard's rho
+//with x^2 + c for increasing c
+fn pollard_rho(n: &BigUint) -> BigUint {
+    let one = BigUint::from(1u64);
+    let mut c = one.clone();
+    loop {
+        let step = |x: &BigUint| (x * x + &c) % n;
+        let mut x = BigUint::from(2u64);
+        let mut y = x.clone();
+        let mut d = one.clone();
+        while d == one {
+            x = step(&x);
+            y = step(&step(&y));
+            let diff = if x > y { &x - &y } else { &y - &x };
+            d = diff.gcd(n);
+        }
+        if d != *n {
+            return d;
+        }
+        c += 1u64;
+    }
+}
+
+//distinct prime factors of n in increasing order
+fn prime_factors(n: &BigUint) -> Vec<BigUint> {
+    let mut result = vec![];
+    let mut n = n.clone();
+    for &p in SMALL_PRIMES.iter() {
+        let p = BigUint::from(p);
+        if (&n % &p) == BigUint::from(0u64) {
+            while (&n % &p) == BigUint::from(0u64) {
+                n /= &p;
+            }
+            result.push(p);
+        }
+    }
+    let mut pending = vec![n];
+    while let Some(m) = pending.pop() {
+        if m == BigUint::from(1u64) {
+            continue;
+        }
+        if is_probable_prime(&m) {
+            result.push(m);
+        } else {
+            let d = pollard_rho(&m);
+            pending.push(&m / &d);
+            pending.push(d);
+        }
+    }
+    result.sort();
+    result.dedup();
+    result
+}
+
+impl<T: Unsigned> BinaryRing<T> {
+    fn reduce(&self, value: &[T], modulus: &[T]) -> Vec<T> {
+        //only used with a non-zero modulus
+        self.divide_slices(value, modulus).unwrap().remainder
+    }
+
+    fn mul_mod(&self, a: &[T], b: &[T], modulus: &[T]) -> Vec<T> {
+        self.reduce(&BinaryRing::mul_slices(a, b), modulus)
+    }
+
+    fn pow_mod(&self, base: &[T], exponent: &BigUint, modulus: &[T]) -> Vec<T> {
+        let mut result = self.reduce(&self.one(), modulus);
+        for i in (0..exponent.bits()).rev() {
+            result = self.mul_mod(&result, &result, modulus);
+            if ((exponent >> i) & BigUint::from(1u64)) == BigUint::from(1u64) {
+                result = self.mul_mod(&result, base, modulus);
+            }
+        }
+        result
+    }
+
+    fn gcd(&self, a: &[T], b: &[T]) -> Vec<T> {
+        let mut b = b.to_vec();
+        BinaryRing::clean_up(&mut b);
+        if b.is_empty() {
+            let mut a = a.to_vec();
+            BinaryRing::clean_up(&mut a);
+            return a;
+        }
+        //gcd(a, b) = gcd(b, a mod b), so a is reduced rather than copied
+        let mut a = self.reduce(a, &b);
+        std::mem::swap(&mut a, &mut b);
+        while !b.is_empty() {
+            let remainder = self.reduce(&a, &b);
+            a = b;
+            b = remainder;
+        }
+        a
+    }
+
+    //Polynomial with the given exponents set, e.g. [8, 4, 3, 1, 0] for x^8 + x^4 + x^3 + x + 1
+    pub fn from_exponents(&self, exponents: &[usize]) -> Vec<T> {
+        let bits = T::BITS as usize;
+        let mut result = vec![T::ZERO; exponents.iter().max().map_or(0, |e| e / bits + 1)];
+        for &e in exponents {
+            result[e / bits] ^= T::ONE << (e % bits) as u32;
+        }
+        BinaryRing::clean_up(&mut result);
+        result
+    }
+
+    //Ben-Or's test: value of degree n is irreducible iff gcd(x^(2^i) - x, value) = 1 for every
+    //i <= n / 2, since otherwise it has a factor of degree dividing i. Constants are not
+    //irreducible.
+    pub fn is_irreducible(&self, value: &[T]) -> bool {
+        let degree = BinaryRing::slice_degree(value);
+        if degree < 1 {
+            return false;
+        }
+        let x = self.reduce(&[T::ONE << 1], value);
+        let mut power = x.clone();
+        for _ in 0..degree / 2 {
+            power = self.mul_mod(&power, &power, value);
+            if self.gcd(value, &self.add(&power, &x)) != self.one() {
+                return false;
+            }
+        }
+        true
+    }
+
+    //An irreducible value of degree n is primitive when x generates the multiplicative group
+    //of GF(2)[x] / value, that is x^((2^n - 1) / r) != 1 for every prime r dividing 2^n - 1
+    pub fn is_primitive(&self, value: &[T]) -> bool {
+        if !self.is_irreducible(value) || !self.bit_at(value, 0) {
+            return false;
+        }
+        let order =
+            (BigUint::from(1u64) << BinaryRing::slice_degree(value) as usize) - BigUint::from(1u64);
+        let x = [T::ONE << 1];
+        prime_factors(&order)
+            .iter()
+            .all(|r| self.pow_mod(&x, &(&order / r), value) != self.one())
+    }
+
+    //Irreducible x^n + x^k + 1 with the smallest k, or failing that irreducible
+    //x^n + x^a + x^b + x^c + 1 with the smallest a, then b, then c. None if there is neither.
+    pub fn lowest_weight_irreducible(&self, degree: usize) -> Option<Vec<T>> {
+        self.find_sparse(degree, |value| self.is_irreducible(value))
+    }
+
+    //Same order of search as lowest_weight_irreducible, keeping only primitive polynomials
+    pub fn lowest_weight_primitive(&self, degree: usize) -> Option<Vec<T>> {
+        self.find_sparse(degree, |value| self.is_primitive(value))
+    }
+
+    fn find_sparse<P: Fn(&[T]) -> bool>(&self, degree: usize, accept: P) -> Option<Vec<T>> {
+        for k in 1..degree {
+            let candidate = self.from_exponents(&[degree, k, 0]);
+            if accept(&candidate) {
+                return Some(candidate);
+            }
+        }
+        for a in 3..degree {
+            for b in 2..a {
+                for c in 1..b {
+                    let candidate = self.from_exponents(&[degree, a, b, c, 0]);
+                    if accept(&candidate) {
+                        return Some(candidate);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    //multiplicative order of x modulo value by brute force, for small degrees
+    fn order_of_x(ring: &BinaryRing<u8>, value: &[u8]) -> Option<usize> {
+        let x = ring.reduce(&[0b10], value);
+        let mut power = x.clone();
+        for order in 1..=(1 << BinaryRing::slice_degree(value)) {
+            if power == ring.one() {
+                return Some(order);
+            }
+            power = ring.mul_mod(&power, &x, value);
+        }
+        None
+    }
+
+    #[test]
+    fn test_prime_factors() {
+        let factors = prime_factors(&((BigUint::from(1u64) << 64usize) - BigUint::from(1u64)));
+        let expected: Vec<BigUint> = [3u64, 5, 17, 257, 641, 65537, 6700417]
+            .iter()
+            .map(|&p| BigUint::from(p))
+            .collect();
+        assert_eq!(factors, expected);
+        let mersenne = (BigUint::from(1u64) << 61usize) - BigUint::from(1u64);
+        assert!(is_probable_prime(&mersenne));
+        assert_eq!(prime_factors(&mersenne), vec![mersenne]);
+        //2^67 - 1 = 193707721 * 761838257287
+        let composite = (BigUint::from(1u64) << 67usize) - BigUint::from(1u64);
+        assert_eq!(
+            prime_factors(&composite),
+            vec![BigUint::from(193707721u64), BigUint::from(761838257287u64)]
+        );
+    }
+
+    #[test]
+    fn test_small_polynomials() {
+        let ring = BinaryRing::<u8>::new();
+        //the AES modulus is irreducible but x only has order 51
+        let aes = ring.from_exponents(&[8, 4, 3, 1, 0]);
+        assert!(ring.is_irreducible(&aes));
+        assert!(!ring.is_primitive(&aes));
+        assert_eq!(order_of_x(&ring, &aes), Some(51));
+        assert!(ring.is_primitive(&ring.from_exponents(&[8, 4, 3, 2, 0])));
+        assert!(ring.is_primitive(&ring.from_exponents(&[4, 1, 0])));
+        //x^4 + x^3 + x^2 + x + 1 divides x^5 - 1
+        assert!(!ring.is_primitive(&ring.from_exponents(&[4, 3, 2, 1, 0])));
+        assert!(ring.is_irreducible(&[0b10]));
+        assert!(!ring.is_primitive(&[0b10]));
+        assert!(ring.is_primitive(&[0b11]));
+        assert!(!ring.is_irreducible(&[0b1]));
+        assert!(!ring.is_irreducible(&[]));
+        assert_eq!(ring.lowest_weight_irreducible(1), None);
+        assert_eq!(ring.lowest_weight_irreducible(8), Some(aes));
+    }
+
+    #[test]
+    fn test_default_moduli_are_lowest_weight() {
+        //every BinaryField default is x^BITS plus its mod substractor
+        fn check<T: Unsigned>(mod_substractor: T) {
+            let ring = BinaryRing::<T>::new();
+            let modulus = vec![mod_substractor, T::ONE];
+            assert!(ring.is_irreducible(&modulus));
+            assert_eq!(
+                ring.lowest_weight_irreducible(T::BITS as usize),
+                Some(modulus)
+            );
+        }
+        check(0b11011u8);
+        check(0b101011u16);
+        check(0b10001101u32);
+        check(0b11011u64);
+        check(0b10000111u128);
+    }
+
+    #[test]
+    fn test_lowest_weight_primitive() {
+        let ring = BinaryRing::<u64>::new();
+        for degree in 2..=20 {
+            let value = ring.lowest_weight_primitive(degree).unwrap();
+            assert_eq!(ring.degree(&value), degree as i32);
+            assert!(ring.is_primitive(&value));
+        }
+        //x^7 + x + 1 is the first primitive trinomial of degree 7
+        assert_eq!(
+            ring.lowest_weight_primitive(7),
+            Some(ring.from_exponents(&[7, 1, 0]))
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_against_brute_force(value in 2u16..512) {
+            let ring = BinaryRing::<u8>::new();
+            let value = vec![value as u8, (value >> 8) as u8];
+            let mut value = value;
+            BinaryRing::clean_up(&mut value);
+            let factors = ring.factor(&value).unwrap();
+            let irreducible = factors.len() == 1 && factors[0].1 == 1;
+            assert_eq!(ring.is_irreducible(&value), irreducible);
+            let full_order = (1 << ring.degree(&value)) - 1;
+            let primitive = irreducible && order_of_x(&ring, &value) == Some(full_order);
+            assert_eq!(ring.is_primitive(&value), primitive);
+        }
+    }
+}
@@ -0,0 +1,227 @@
+/*
+ * Copyright  2020 Debasish Ray Chawdhuri
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do
+ * so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+ * EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+ * MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT
+ * HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+ * WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+ * DEALINGS IN THE SOFTWARE.
+ */
+use crate::error::Error;
+use crate::field::{Rational, RationalField};
+use crate::int_ring::BigIntRing;
+use crate::{Field, Matrix, Ring};
+use num::BigInt;
+
+fn dot(x: &[Rational], y: &[Rational]) -> Rational {
+    let q = RationalField;
+    x.iter()
+        .zip(y.iter())
+        .fold(q.zero(), |sum, (a, b)| q.add(&sum, &q.mul(a, b)))
+}
+
+//Gram-Schmidt coefficients mu[i][j] = <b_i, b*_j> / <b*_j, b*_j> for j < i, and the squared
+//lengths <b*_i, b*_i> of the orthogonalised rows
+fn gram_schmidt(basis: &Matrix<BigIntRing>) -> Result<(Vec<Vec<Rational>>, Vec<Rational>), Error> {
+    let q = RationalField;
+    let n = basis.rows;
+    let mut mu = vec![vec![q.zero(); n]; n];
+    let mut norms: Vec<Rational> = Vec::with_capacity(n);
+    let mut orthogonal: Vec<Vec<Rational>> = Vec::with_capacity(n);
+    for (i, mu_row) in mu.iter_mut().enumerate() {
+        let row: Vec<Rational> = basis
+            .row(i)
+            .iter()
+            .map(|x| Rational::from_integer(x.clone()))
+            .collect();
+        let mut star = row.clone();
+        for j in 0..i {
+            mu_row[j] = q.mul(&dot(&row, &orthogonal[j]), &q.inv(&norms[j])?);
+            for (s, o) in star.iter_mut().zip(orthogonal[j].iter()) {
+                *s = q.add(s, &q.neg(&q.mul(&mu_row[j], o)));
+            }
+        }
+        let norm = dot(&star, &star);
+        if norm == q.zero() {
+            return Err(Error::LinearlyDependentLatticeBasis);
+        }
+        norms.push(norm);
+        orthogonal.push(star);
+    }
+    Ok((mu, norms))
+}
+
+impl<'a> Matrix<'a, BigIntRing> {
+    //LLL reduction with the usual delta = 3/4
+    pub fn lll_reduce(&self) -> Result<(Matrix<'a, BigIntRing>, Matrix<'a, BigIntRing>), Error> {
+        let delta = Rational::new(BigInt::from(3), BigInt::from(4))?;
+        self.lll_reduce_with_delta(&delta)
+    }
+
+    //Reduces the lattice spanned by the rows, which have to be linearly independent. Returns
+    //(B, U) with B = U * A, U unimodular and B LLL reduced for the given delta in (1/4, 1].
+    //All Gram-Schmidt data is kept as exact rationals.
+    pub fn lll_reduce_with_delta(
+        &self,
+        delta: &Rational,
+    ) -> Result<(Matrix<'a, BigIntRing>, Matrix<'a, BigIntRing>), Error> {
+        let q = RationalField;
+        let quarter = Rational::new(BigInt::from(1), BigInt::from(4))?;
+        if *delta <= quarter || *delta > q.one() {
+            return Err(Error::LllDeltaOutOfRange);
+        }
+        let n = self.rows;
+        let mut basis = self.clone();
+        let mut transform = Matrix::identity(self.ring.clone(), n);
+        let (mut mu, mut norms) = gram_schmidt(self)?;
+
+        let mut k = 1;
+        while k < n {
+            //size reduction, afterwards |mu[k][j]| <= 1/2 for every j < k
+            for j in (0..k).rev() {
+                let r = mu[k][j].round();
+                if r == BigInt::from(0) {
+                    continue;
+                }
+                let minus_r = -&r;
+                basis.add_multiple_of(k, j, &minus_r);
+                transform.add_multiple_of(k, j, &minus_r);
+                let r = Rational::from_integer(r);
+                let (top, bottom) = mu.split_at_mut(k);
+                for (target, source) in bottom[0].iter_mut().zip(top[j].iter()).take(j) {
+                    *target = q.add(target, &q.neg(&q.mul(&r, source)));
+                }
+                mu[k][j] = q.add(&mu[k][j], &q.neg(&r));
+            }
+
+            //Lovasz condition |b*_k|^2 >= (delta - mu[k][k-1]^2) |b*_(k-1)|^2
+            let m = mu[k][k - 1].clone();
+            let bound = q.mul(&q.add(delta, &q.neg(&q.mul(&m, &m))), &norms[k - 1]);
+            if norms[k] >= bound {
+                k += 1;
+                continue;
+            }
+
+            //swap b_k and b_(k-1) and update the Gram-Schmidt data in place
+            basis.swap_rows(k, k - 1);
+            transform.swap_rows(k, k - 1);
+            let new_norm = q.add(&norms[k], &q.mul(&q.mul(&m, &m), &norms[k - 1]));
+            let new_inv = q.inv(&new_norm)?;
+            mu[k][k - 1] = q.mul(&q.mul(&m, &norms[k - 1]), &new_inv);
+            norms[k] = q.mul(&q.mul(&norms[k - 1], &norms[k]), &new_inv);
+            norms[k - 1] = new_norm;
+            let (top, bottom) = mu.split_at_mut(k);
+            top[k - 1][..k - 1].swap_with_slice(&mut bottom[0][..k - 1]);
+            let new_mu = mu[k][k - 1].clone();
+            for row in mu.iter_mut().skip(k + 1) {
+                let t = row[k].clone();
+                row[k] = q.add(&row[k - 1], &q.neg(&q.mul(&m, &t)));
+                row[k - 1] = q.add(&t, &q.mul(&new_mu, &row[k]));
+            }
+            k = if k > 1 { k - 1 } else { 1 };
+        }
+        Ok((basis, transform))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big_matrix(ring: &BigIntRing, rows: Vec<Vec<i64>>) -> Matrix<'_, BigIntRing> {
+        Matrix::new(
+            ring,
+            rows.into_iter()
+                .map(|row| row.into_iter().map(BigInt::from).collect())
+                .collect(),
+        )
+    }
+
+    fn check_reduced(original: &Matrix<BigIntRing>, delta: &Rational) {
+        let q = RationalField;
+        let (reduced, transform) = original.lll_reduce_with_delta(delta).unwrap();
+        assert_eq!(transform.checked_mul(original).unwrap(), reduced);
+        let det = transform.division_free_determinant().unwrap();
+        assert!(det == BigInt::from(1) || det == BigInt::from(-1));
+        let (mu, norms) = gram_schmidt(&reduced).unwrap();
+        let half = Rational::new(BigInt::from(1), BigInt::from(2)).unwrap();
+        for (i, row) in mu.iter().enumerate() {
+            for m in row.iter().take(i) {
+                assert!(*m <= half && q.neg(m) <= half);
+            }
+        }
+        for k in 1..reduced.rows() {
+            let m = &mu[k][k - 1];
+            let bound = q.mul(&q.add(delta, &q.neg(&q.mul(m, m))), &norms[k - 1]);
+            assert!(norms[k] >= bound);
+        }
+    }
+
+    #[test]
+    fn test_lll_reduce() {
+        let ring = BigIntRing;
+        let basis = big_matrix(&ring, vec![vec![1, 1, 1], vec![-1, 0, 2], vec![3, 5, 6]]);
+        let (reduced, _) = basis.lll_reduce().unwrap();
+        assert_eq!(
+            reduced,
+            big_matrix(&ring, vec![vec![0, 1, 0], vec![1, 0, 1], vec![-2, 0, 1]])
+        );
+        let delta = Rational::new(BigInt::from(3), BigInt::from(4)).unwrap();
+        check_reduced(&basis, &delta);
+    }
+
+    #[test]
+    fn test_lll_knapsack_style_basis() {
+        let ring = BigIntRing;
+        //identity next to a column of large weights, the shape used for subset sum attacks
+        let weights = [366, 385, 103, 872, 1018, 447];
+        let rows = weights
+            .iter()
+            .enumerate()
+            .map(|(i, &w)| {
+                let mut row = vec![0; weights.len() + 1];
+                row[i] = 1;
+                row[weights.len()] = w * 1000;
+                row
+            })
+            .collect();
+        let basis = big_matrix(&ring, rows);
+        check_reduced(
+            &basis,
+            &Rational::new(BigInt::from(99), BigInt::from(100)).unwrap(),
+        );
+        check_reduced(
+            &basis,
+            &Rational::new(BigInt::from(1), BigInt::from(2)).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_lll_errors() {
+        let ring = BigIntRing;
+        let basis = big_matrix(&ring, vec![vec![1, 2], vec![2, 4]]);
+        assert!(matches!(
+            basis.lll_reduce(),
+            Err(Error::LinearlyDependentLatticeBasis)
+        ));
+        let basis = big_matrix(&ring, vec![vec![1, 2], vec![3, 4]]);
+        let quarter = Rational::new(BigInt::from(1), BigInt::from(4)).unwrap();
+        assert!(matches!(
+            basis.lll_reduce_with_delta(&quarter),
+            Err(Error::LllDeltaOutOfRange)
+        ));
+    }
+}
@@ -24,14 +24,30 @@
 extern crate num_bigint as num;
 use core::fmt::Debug;
 
+mod arith;
 pub mod binary;
+pub mod binary_table;
+pub mod block;
 pub mod error;
 pub mod euclidian_domain;
+pub mod factorization;
 pub mod field;
+pub mod fraction;
 pub mod int_ring;
-use std::ops::*;
+pub mod interpolation;
+pub mod irreducibility;
+pub mod lattice;
+pub mod lu;
+pub mod normal_form;
+mod ops;
+pub mod pivoting;
+pub mod polynomial;
+pub mod vector;
+use std::ops::{Deref, Index, IndexMut};
+use std::sync::Arc;
 
 use error::Error;
+use pivoting::Pivoting;
 
 pub trait Ring {
     type RingMember: Clone + PartialEq;
@@ -44,6 +60,28 @@ pub trait Ring {
 
 pub trait Field: Ring {
     fn inv(&self, value: &Self::RingMember) -> Result<Self::RingMember, Error>;
+
+    //size of a value, used to rank pivot candidates. Exact fields only need to tell zero
+    //apart from everything else
+    fn magnitude(&self, value: &Self::RingMember) -> f64 {
+        if *value == self.zero() {
+            0f64
+        } else {
+            1f64
+        }
+    }
+
+    //Primitive root of unity of the given order, which is a power of two, if the field has
+    //one. Polynomial multiplication uses it for the number theoretic transform.
+    #[allow(unused)]
+    fn root_of_unity(&self, order: usize) -> Option<Self::RingMember> {
+        None
+    }
+
+    //pivoting used by the elimination routines of Matrix
+    fn pivoting(&self) -> Pivoting {
+        Pivoting::FirstNonZero
+    }
 }
 
 #[macro_export]
@@ -61,12 +99,76 @@ macro_rules! matrix {
     };
 }
 
-#[derive(PartialEq, Clone, Debug)]
+//The ring a matrix works over. It is either borrowed, which is what Matrix::new gives, or
+//shared through an Arc so that the matrix does not depend on the lifetime of a local ring and
+//can be stored in long-lived structs or sent to other threads.
+pub enum RingRef<'a, F> {
+    Borrowed(&'a F),
+    Shared(Arc<F>),
+}
+
+impl<'a, F> Deref for RingRef<'a, F> {
+    type Target = F;
+
+    fn deref(&self) -> &F {
+        match self {
+            RingRef::Borrowed(ring) => ring,
+            RingRef::Shared(ring) => ring,
+        }
+    }
+}
+
+impl<'a, F> Clone for RingRef<'a, F> {
+    fn clone(&self) -> Self {
+        match self {
+            RingRef::Borrowed(ring) => RingRef::Borrowed(ring),
+            RingRef::Shared(ring) => RingRef::Shared(ring.clone()),
+        }
+    }
+}
+
+impl<'a, F: PartialEq> PartialEq for RingRef<'a, F> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<'a, F: Debug> Debug for RingRef<'a, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<'a, F> From<&'a F> for RingRef<'a, F> {
+    fn from(ring: &'a F) -> Self {
+        RingRef::Borrowed(ring)
+    }
+}
+
+impl<'a, F> From<Arc<F>> for RingRef<'a, F> {
+    fn from(ring: Arc<F>) -> Self {
+        RingRef::Shared(ring)
+    }
+}
+
+#[derive(PartialEq, Debug)]
 pub struct Matrix<'a, F: Ring> {
-    ring: &'a F,
+    ring: RingRef<'a, F>,
     rows: usize,
     columns: usize,
-    data: Vec<Vec<F::RingMember>>,
+    //row-major storage, the element at (row, col) lives at row * columns + col
+    data: Vec<F::RingMember>,
+}
+
+impl<'a, F: Ring> Clone for Matrix<'a, F> {
+    fn clone(&self) -> Self {
+        Matrix {
+            ring: self.ring.clone(),
+            rows: self.rows,
+            columns: self.columns,
+            data: self.data.clone(),
+        }
+    }
 }
 
 impl<'a, F: Ring> Matrix<'a, F> {
@@ -79,121 +181,568 @@ impl<'a, F: Ring> Matrix<'a, F> {
     }
 
     pub fn value_at(&self, row: usize, col: usize) -> F::RingMember {
-        self.data[row][col].clone()
+        self.at(row, col).clone()
     }
-}
 
-impl<'a, F: Field> Matrix<'a, F> {
-    fn swap_rows(
-        &self,
-        data1: &mut Vec<Vec<F::RingMember>>,
-        data2: &mut Vec<Vec<F::RingMember>>,
-        r1: usize,
-        r2: usize,
-    ) {
+    fn at(&self, row: usize, col: usize) -> &F::RingMember {
+        &self.data[row * self.columns + col]
+    }
+
+    fn at_mut(&mut self, row: usize, col: usize) -> &mut F::RingMember {
+        &mut self.data[row * self.columns + col]
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: F::RingMember) {
+        self[(row, col)] = value;
+    }
+
+    pub fn row(&self, row: usize) -> &[F::RingMember] {
+        &self.data[row * self.columns..(row + 1) * self.columns]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [F::RingMember] {
+        &mut self.data[row * self.columns..(row + 1) * self.columns]
+    }
+
+    pub fn column(&self, col: usize) -> Column<'_, F::RingMember> {
+        assert!(col < self.columns, "column {} out of range", col);
+        Column {
+            //a matrix without rows has an empty buffer
+            data: &self.data[col.min(self.data.len())..],
+            stride: self.columns,
+            len: self.rows,
+        }
+    }
+
+    pub fn row_iter(&self) -> impl Iterator<Item = &[F::RingMember]> + '_ {
+        (0..self.rows).map(move |i| self.row(i))
+    }
+
+    pub fn column_iter(&self) -> impl Iterator<Item = Column<'_, F::RingMember>> + '_ {
+        (0..self.columns).map(move |j| self.column(j))
+    }
+
+    pub fn swap_rows(&mut self, r1: usize, r2: usize) {
         if r1 == r2 {
             return;
         }
-        data1.swap(r1, r2);
-        data2.swap(r1, r2);
+        let (low, high) = if r1 < r2 { (r1, r2) } else { (r2, r1) };
+        let columns = self.columns;
+        let (top, bottom) = self.data.split_at_mut(high * columns);
+        top[low * columns..(low + 1) * columns].swap_with_slice(&mut bottom[..columns]);
     }
-    fn add_multiple_of(
-        &self,
-        data1: &mut Vec<Vec<F::RingMember>>,
-        data2: &mut Vec<Vec<F::RingMember>>,
-        r1: usize,
-        r2: usize,
-        mul: F::RingMember,
-    ) {
-        for i in 0..data1[r1].len() {
-            data1[r1][i] = self
-                .ring
-                .add(&data1[r1][i], &self.ring.mul(&mul, &data1[r2][i]));
-            data2[r1][i] = self
-                .ring
-                .add(&data2[r1][i], &self.ring.mul(&mul, &data2[r2][i]));
-        }
-    }
-    fn scale_row(
-        &self,
-        data1: &mut Vec<Vec<F::RingMember>>,
-        data2: &mut Vec<Vec<F::RingMember>>,
-        r1: usize,
-        mul: F::RingMember,
-    ) {
-        for i in 0..data1[r1].len() {
-            data1[r1][i] = self.ring.mul(&mul, &data1[r1][i]);
-            data2[r1][i] = self.ring.mul(&mul, &data2[r1][i]);
+
+    pub fn swap_columns(&mut self, c1: usize, c2: usize) {
+        if c1 == c2 {
+            return;
+        }
+        for row in self.data.chunks_mut(self.columns) {
+            row.swap(c1, c2);
         }
     }
 
-    fn find_non_zero_pivot(
-        &self,
-        data1: &Vec<Vec<F::RingMember>>,
-        start: usize,
-    ) -> Result<usize, Error> {
-        for i in start..self.rows {
-            if data1[i][start] != self.ring.zero() {
-                return Ok(i);
-            }
+    //row r1 += mul * row r2
+    fn add_multiple_of(&mut self, r1: usize, r2: usize, mul: &F::RingMember) {
+        let ring = &self.ring;
+        let columns = self.columns;
+        let (target, source) = if r1 < r2 {
+            let (top, bottom) = self.data.split_at_mut(r2 * columns);
+            (
+                &mut top[r1 * columns..(r1 + 1) * columns],
+                &bottom[..columns],
+            )
+        } else {
+            let (top, bottom) = self.data.split_at_mut(r1 * columns);
+            (
+                &mut bottom[..columns],
+                &top[r2 * columns..(r2 + 1) * columns],
+            )
+        };
+        for (t, s) in target.iter_mut().zip(source.iter()) {
+            *t = ring.add(t, &ring.mul(mul, s));
+        }
+    }
+
+    fn scale_row(&mut self, r1: usize, mul: &F::RingMember) {
+        let ring = &self.ring;
+        let columns = self.columns;
+        for value in self.data[r1 * columns..(r1 + 1) * columns].iter_mut() {
+            *value = ring.mul(mul, value);
+        }
+    }
+}
+
+impl<'a, F: Ring> Index<(usize, usize)> for Matrix<'a, F> {
+    type Output = F::RingMember;
+
+    fn index(&self, (row, col): (usize, usize)) -> &F::RingMember {
+        assert!(col < self.columns, "column {} out of range", col);
+        self.at(row, col)
+    }
+}
+
+impl<'a, F: Ring> IndexMut<(usize, usize)> for Matrix<'a, F> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut F::RingMember {
+        assert!(col < self.columns, "column {} out of range", col);
+        self.at_mut(row, col)
+    }
+}
+
+//Borrowed view of one column of a matrix. Columns are strided in the row-major buffer,
+//so this can't be a plain slice like a row.
+#[derive(Clone, Copy)]
+pub struct Column<'m, T> {
+    data: &'m [T],
+    stride: usize,
+    len: usize,
+}
+
+impl<'m, T> Column<'m, T> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, i: usize) -> Option<&'m T> {
+        if i < self.len {
+            Some(&self.data[i * self.stride])
+        } else {
+            None
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'m T> + 'm {
+        self.data.iter().step_by(self.stride).take(self.len)
+    }
+
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<'m, T> Index<usize> for Column<'m, T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        match self.get(i) {
+            Some(value) => value,
+            None => panic!("index {} out of range for column of length {}", i, self.len),
         }
-        Err(Error::InversionOfNonInvertibleSquareMatrix)
+    }
+}
+
+impl<'a, F: Field> Matrix<'a, F> {
+    //pivoting of the ring with its tolerance scaled to the entries of the matrix
+    fn scaled_pivoting(&self) -> Pivoting {
+        self.ring
+            .pivoting()
+            .relative_to(&*self.ring, self.data.iter())
+    }
+
+    fn find_pivot_in_column(
+        &self,
+        pivoting: &Pivoting,
+        start_row: usize,
+        column: usize,
+    ) -> Option<usize> {
+        pivoting.select(
+            &*self.ring,
+            (start_row..self.rows).map(|i| (i, self.at(i, column))),
+        )
+    }
+
+    fn find_non_zero_pivot(&self, pivoting: &Pivoting, start: usize) -> Result<usize, Error> {
+        self.find_pivot_in_column(pivoting, start, start)
+            .ok_or(Error::InversionOfNonInvertibleSquareMatrix)
     }
 
     pub fn inverse(&self) -> Result<Self, Error> {
         if self.rows != self.columns {
             return Err(Error::InversionOfRectangularMatrix);
         }
-        let mut data1 = self.data.clone();
-        let mut data2 = Self::one(self.ring, self.rows).data;
+        let mut data1 = self.clone();
+        let mut data2 = Self::identity(self.ring.clone(), self.rows);
+        let pivoting = self.scaled_pivoting();
 
         //triangulation of the matrix. make it an upper triangular matrix
         for i in 0..self.rows {
-            let pivot = self.find_non_zero_pivot(&data1, i);
+            let pivot = data1.find_non_zero_pivot(&pivoting, i);
             match pivot {
                 Ok(p) => {
-                    self.swap_rows(&mut data1, &mut data2, i, p);
+                    data1.swap_rows(i, p);
+                    data2.swap_rows(i, p);
                 }
                 Err(x) => {
                     return Err(x);
                 }
             }
 
-            let d = data1[i][i].clone();
+            let d = data1.at(i, i).clone();
             match self.ring.inv(&d) {
                 Ok(d_inv) => {
-                    self.scale_row(&mut data1, &mut data2, i, d_inv);
+                    data1.scale_row(i, &d_inv);
+                    data2.scale_row(i, &d_inv);
                 }
                 Err(_) => {
                     return Err(Error::InversionOfNonInvertibleSquareMatrix);
                 }
             }
             for j in i + 1..self.rows {
-                let mult = self.ring.neg(&data1[j][i]);
-                self.add_multiple_of(&mut data1, &mut data2, j, i, mult);
+                if *data1.at(j, i) == self.ring.zero() {
+                    continue;
+                }
+                let mult = self.ring.neg(data1.at(j, i));
+                data1.add_multiple_of(j, i, &mult);
+                data2.add_multiple_of(j, i, &mult);
             }
         }
 
-        //Now we make it a identity matrix. Notice that all diagonal entries are already 1
+        //Now we make it a identity matrix. Notice that all diagonal entries are already 1,
+        //and row x of data1 is already a unit row when it is used
 
         for i in 0..self.rows {
             for j in i + 1..self.rows {
                 let x = self.rows - i - 1;
                 let y = self.rows - j - 1;
-                let mult = self.ring.neg(&data1[y][x].clone());
-                self.add_multiple_of(&mut data1, &mut data2, y, x, mult);
+                if *data1.at(y, x) == self.ring.zero() {
+                    continue;
+                }
+                let mult = self.ring.neg(data1.at(y, x));
+                *data1.at_mut(y, x) = self.ring.zero();
+                data2.add_multiple_of(y, x, &mult);
+            }
+        }
+
+        Ok(data2)
+    }
+
+    //Gaussian elimination to an upper triangular matrix; the determinant is the
+    //product of the pivots, with a sign flip for every row swap
+    pub fn determinant(&self) -> Result<F::RingMember, Error> {
+        if self.rows != self.columns {
+            return Err(Error::DeterminantOfRectangularMatrix);
+        }
+        let mut data = self.clone();
+        let mut det = self.ring.one();
+        let pivoting = self.scaled_pivoting();
+
+        for i in 0..self.rows {
+            let p = match data.find_non_zero_pivot(&pivoting, i) {
+                Ok(p) => p,
+                Err(_) => return Ok(self.ring.zero()),
+            };
+            if p != i {
+                data.swap_rows(i, p);
+                det = self.ring.neg(&det);
+            }
+            det = self.ring.mul(&det, data.at(i, i));
+            let d_inv = self.ring.inv(data.at(i, i))?;
+            for j in i + 1..self.rows {
+                let mult = self.ring.neg(&self.ring.mul(data.at(j, i), &d_inv));
+                data.add_multiple_of(j, i, &mult);
+            }
+        }
+        Ok(det)
+    }
+
+    //Gauss-Jordan elimination in place, looking for pivots only among the first `columns`
+    //columns. The rows are transformed over their full length. Returns the pivot columns.
+    fn gauss_jordan(&mut self, columns: usize) -> Vec<usize> {
+        let mut pivots = Vec::new();
+        let mut row = 0;
+        //the scale comes from the columns searched for pivots, not from any right hand sides
+        let pivoting = self.ring.pivoting().relative_to(
+            &*self.ring,
+            (0..self.rows).flat_map(|i| self.row(i)[..columns].iter()),
+        );
+
+        for col in 0..columns {
+            if row == self.rows {
+                break;
+            }
+            let p = match self.find_pivot_in_column(&pivoting, row, col) {
+                Some(p) => p,
+                None => {
+                    //whatever is left below is rounding noise
+                    for j in row..self.rows {
+                        *self.at_mut(j, col) = self.ring.zero();
+                    }
+                    continue;
+                }
+            };
+            self.swap_rows(row, p);
+            //the pivot is non-zero, so it is invertible in a field
+            if let Ok(d_inv) = self.ring.inv(self.at(row, col)) {
+                self.scale_row(row, &d_inv);
+            }
+            for j in 0..self.rows {
+                if j == row {
+                    continue;
+                }
+                if !pivoting.is_negligible(&*self.ring, self.at(j, col)) {
+                    let mult = self.ring.neg(self.at(j, col));
+                    self.add_multiple_of(j, row, &mult);
+                }
+                //exact zero, even if the elimination left a rounding residue
+                *self.at_mut(j, col) = self.ring.zero();
+            }
+            pivots.push(col);
+            row += 1;
+        }
+        pivots
+    }
+
+    //Gauss-Jordan elimination on a (possibly rectangular) matrix. Returns the reduced row
+    //echelon form together with the indices of the pivot columns
+    pub fn rref(&self) -> (Matrix<'a, F>, Vec<usize>) {
+        let mut reduced = self.clone();
+        let pivots = reduced.gauss_jordan(self.columns);
+        (reduced, pivots)
+    }
+
+    pub fn rank(&self) -> usize {
+        self.rref().1.len()
+    }
+
+    pub fn nullity(&self) -> usize {
+        self.columns - self.rank()
+    }
+
+    //Solves A X = B where every column of B is a separate right hand side. A may be
+    //rectangular or singular. Every solution of the system has the form
+    //particular + homogeneous * Y for an arbitrary matrix Y.
+    pub fn solve(&self, rhs: &Matrix<F>) -> Result<LinearSystemSolution<'a, F>, Error> {
+        if self.rows != rhs.rows {
+            return Err(Error::DimensionMismatchForLinearSystem(
+                self.rows,
+                self.columns,
+                rhs.rows,
+                rhs.columns,
+            ));
+        }
+        let mut augmented = self.hstack(rhs)?;
+        let pivots = augmented.gauss_jordan(self.columns);
+
+        //a zero row on the left with a non-zero entry on the right means 0 = c. Whether c is
+        //zero is decided relative to both sides, as it collects the rounding of the elimination.
+        let consistency = self
+            .ring
+            .pivoting()
+            .relative_to(&*self.ring, self.data.iter().chain(rhs.data.iter()));
+        for i in pivots.len()..self.rows {
+            if augmented.row(i)[self.columns..]
+                .iter()
+                .any(|x| !consistency.is_negligible(&*self.ring, x))
+            {
+                return Err(Error::InconsistentLinearSystem);
+            }
+        }
+
+        let mut particular = Matrix {
+            ring: self.ring.clone(),
+            rows: self.columns,
+            columns: rhs.columns,
+            data: vec![self.ring.zero(); self.columns * rhs.columns],
+        };
+        for (i, &p) in pivots.iter().enumerate() {
+            particular
+                .row_mut(p)
+                .clone_from_slice(&augmented.row(i)[self.columns..]);
+        }
+
+        Ok(LinearSystemSolution {
+            particular,
+            homogeneous: self.null_space_basis(&augmented, &pivots),
+        })
+    }
+
+    //Monic polynomial p of least degree with p(A) = 0, lowest degree first. The powers
+    //I, A, A^2, ... are flattened into the columns of one matrix; the first column that is
+    //not a pivot of its reduced row echelon form is the first power that depends on the
+    //previous ones, and its column in the reduced form holds that dependency.
+    pub fn minimal_polynomial(&self) -> Result<Vec<F::RingMember>, Error> {
+        if self.rows != self.columns {
+            return Err(Error::PolynomialOfRectangularMatrix);
+        }
+        let n = self.rows;
+        let mut powers = Matrix::zeros(self.ring.clone(), n * n, n + 1);
+        let mut power = Matrix::identity(self.ring.clone(), n);
+        for k in 0..=n {
+            for (i, value) in power.data.iter().enumerate() {
+                *powers.at_mut(i, k) = value.clone();
+            }
+            if k < n {
+                power = power.checked_mul(self)?;
+            }
+        }
+        let (reduced, pivots) = powers.rref();
+        //by Cayley-Hamilton A^n depends on the lower powers, so this always exists
+        let degree = (0..=n).find(|&k| pivots.get(k) != Some(&k)).unwrap_or(n);
+        let mut coeffs: Vec<F::RingMember> = (0..degree)
+            .map(|i| self.ring.neg(reduced.at(i, degree)))
+            .collect();
+        coeffs.push(self.ring.one());
+        Ok(coeffs)
+    }
+
+    //Reads off a basis of the null space from rows already in reduced row echelon form,
+    //one basis vector (as a column) for every free column
+    fn null_space_basis(&self, reduced: &Matrix<F>, pivots: &[usize]) -> Matrix<'a, F> {
+        let free_columns: Vec<usize> = (0..self.columns).filter(|c| !pivots.contains(c)).collect();
+        let mut basis = Matrix {
+            ring: self.ring.clone(),
+            rows: self.columns,
+            columns: free_columns.len(),
+            data: vec![self.ring.zero(); self.columns * free_columns.len()],
+        };
+        for (k, &f) in free_columns.iter().enumerate() {
+            *basis.at_mut(f, k) = self.ring.one();
+            for (i, &p) in pivots.iter().enumerate() {
+                *basis.at_mut(p, k) = self.ring.neg(reduced.at(i, f));
+            }
+        }
+        basis
+    }
+
+    //basis of {x : A x = 0}, the vectors being the columns of the result
+    pub fn kernel(&self) -> Matrix<'a, F> {
+        let (reduced, pivots) = self.rref();
+        self.null_space_basis(&reduced, &pivots)
+    }
+
+    //basis of {y : y^T A = 0}, the vectors being the columns of the result
+    pub fn left_kernel(&self) -> Matrix<'a, F> {
+        self.transpose().kernel()
+    }
+
+    //basis of the column space, made of the pivot columns of the matrix itself
+    pub fn image(&self) -> Matrix<'a, F> {
+        let pivots = self.rref().1;
+        Matrix {
+            ring: self.ring.clone(),
+            rows: self.rows,
+            columns: pivots.len(),
+            data: (0..self.rows)
+                .flat_map(|i| pivots.iter().map(move |&p| self.at(i, p).clone()))
+                .collect(),
+        }
+    }
+}
+
+//Result of Matrix::solve. `particular` holds one solution per right hand side as its columns
+//and the columns of `homogeneous` form a basis of the solutions of A X = 0
+#[derive(PartialEq, Clone)]
+pub struct LinearSystemSolution<'a, F: Ring> {
+    pub particular: Matrix<'a, F>,
+    pub homogeneous: Matrix<'a, F>,
+}
+
+impl<'a, F: Ring> Matrix<'a, F> {
+    //Berkowitz algorithm. Returns the coefficients of det(xI - A), highest degree first.
+    //Only ring operations are used, so this works without any division.
+    fn berkowitz_coefficients(&self) -> Vec<F::RingMember> {
+        let mut coeffs = vec![self.ring.one()];
+        for r in 0..self.rows {
+            //q = [1, -a_rr, -R*C, -R*A*C, ..., -R*A^(r-1)*C] where A is the leading r x r block,
+            //R is row r and C is column r restricted to that block
+            let mut q = Vec::with_capacity(r + 2);
+            q.push(self.ring.one());
+            q.push(self.ring.neg(self.at(r, r)));
+            let mut column: Vec<F::RingMember> = (0..r).map(|i| self.at(i, r).clone()).collect();
+            for _ in 0..r {
+                let mut prod = self.ring.zero();
+                for (a, c) in self.row(r).iter().zip(column.iter()) {
+                    prod = self.ring.add(&prod, &self.ring.mul(a, c));
+                }
+                q.push(self.ring.neg(&prod));
+                column = (0..r)
+                    .map(|i| {
+                        let mut sum = self.ring.zero();
+                        for (a, c) in self.row(i).iter().zip(column.iter()) {
+                            sum = self.ring.add(&sum, &self.ring.mul(a, c));
+                        }
+                        sum
+                    })
+                    .collect();
+            }
+
+            //multiply by the (r+2) x (r+1) lower triangular Toeplitz matrix built from q
+            let mut next = vec![self.ring.zero(); r + 2];
+            for (i, value) in next.iter_mut().enumerate() {
+                for (j, c) in coeffs.iter().enumerate().take(i + 1) {
+                    *value = self.ring.add(value, &self.ring.mul(&q[i - j], c));
+                }
+            }
+            coeffs = next;
+        }
+        coeffs
+    }
+
+    //Coefficients of det(xI - A), lowest degree first, so the i-th entry belongs to x^i.
+    //Computed with the division free Berkowitz algorithm, so any commutative ring works.
+    pub fn characteristic_polynomial(&self) -> Result<Vec<F::RingMember>, Error> {
+        if self.rows != self.columns {
+            return Err(Error::PolynomialOfRectangularMatrix);
+        }
+        let mut coeffs = self.berkowitz_coefficients();
+        coeffs.reverse();
+        Ok(coeffs)
+    }
+
+    //p(A) by Horner's rule, with the coefficients of p lowest degree first
+    pub fn evaluate_polynomial(&self, coeffs: &[F::RingMember]) -> Result<Matrix<'a, F>, Error> {
+        if self.rows != self.columns {
+            return Err(Error::PolynomialOfRectangularMatrix);
+        }
+        let mut result = Matrix::zeros(self.ring.clone(), self.rows, self.rows);
+        for c in coeffs.iter().rev() {
+            result = result.checked_mul(self)?;
+            for i in 0..self.rows {
+                *result.at_mut(i, i) = self.ring.add(result.at(i, i), c);
             }
         }
+        Ok(result)
+    }
 
-        return Ok(Matrix::new(self.ring, data2));
+    //determinant using only ring operations, so it is exact over rings like I64Ring and BigIntRing
+    pub fn division_free_determinant(&self) -> Result<F::RingMember, Error> {
+        if self.rows != self.columns {
+            return Err(Error::DeterminantOfRectangularMatrix);
+        }
+        let coeffs = self.berkowitz_coefficients();
+        let constant = coeffs[self.rows].clone();
+        if self.rows % 2 == 0 {
+            Ok(constant)
+        } else {
+            Ok(self.ring.neg(&constant))
+        }
     }
 }
 
 impl<'a, F: Ring> Matrix<'a, F> {
     pub fn new(ring: &'a F, v: Vec<Vec<F::RingMember>>) -> Self {
+        Self::from_rows(RingRef::Borrowed(ring), v)
+    }
+
+    //same as new, but the matrix shares ownership of the ring instead of borrowing it
+    pub fn new_shared(ring: Arc<F>, v: Vec<Vec<F::RingMember>>) -> Self {
+        Self::from_rows(RingRef::Shared(ring), v)
+    }
+
+    fn from_rows(ring: RingRef<'a, F>, v: Vec<Vec<F::RingMember>>) -> Self {
         let rows = v.len();
-        let columns = v[0].len();
-        let data = v;
+        let columns = v.first().map_or(0, |row| row.len());
+        assert!(
+            v.iter().all(|row| row.len() == columns),
+            "All rows of a matrix must have the same length"
+        );
+        let data = v.into_iter().flatten().collect();
         Matrix {
             ring,
             rows,
@@ -206,58 +755,89 @@ impl<'a, F: Ring> Matrix<'a, F> {
         ring: &'a F,
         data: [[F::RingMember; COLS]; ROWS],
     ) -> Self {
-        let mut v = Vec::new();
-        for i in 0..ROWS {
-            let row = data[i].to_vec();
-            v.push(row);
-        }
         Matrix {
-            ring,
+            ring: RingRef::Borrowed(ring),
             rows: ROWS,
             columns: COLS,
-            data: v,
+            data: data.iter().flat_map(|row| row.iter().cloned()).collect(),
         }
     }
 
     pub fn one(ring: &'a F, rows: usize) -> Self {
-        let mut data = vec![vec![ring.zero(); rows]; rows];
+        Self::identity(RingRef::Borrowed(ring), rows)
+    }
+
+    pub fn zero(ring: &'a F, rows: usize) -> Self {
+        Self::zeros(RingRef::Borrowed(ring), rows, rows)
+    }
+
+    fn identity(ring: RingRef<'a, F>, rows: usize) -> Self {
+        let one = ring.one();
+        let mut ans = Self::zeros(ring, rows, rows);
         for i in 0..rows {
-            data[i][i] = ring.one();
+            *ans.at_mut(i, i) = one.clone();
         }
+        ans
+    }
+
+    fn zeros(ring: RingRef<'a, F>, rows: usize, columns: usize) -> Self {
+        let data = vec![ring.zero(); rows * columns];
         Matrix {
             ring,
             rows,
-            columns: rows,
+            columns,
             data,
         }
     }
 
-    pub fn zero(ring: &'a F, rows: usize) -> Self {
-        let data = vec![vec![ring.zero(); rows]; rows];
+    pub fn ring(&self) -> &F {
+        &self.ring
+    }
+
+    //Detaches the matrix from the lifetime of its ring. A borrowed ring is cloned into
+    //an Arc, a shared one is kept as it is.
+    pub fn into_shared(self) -> Matrix<'static, F>
+    where
+        F: Clone + 'static,
+    {
+        let ring = match self.ring {
+            RingRef::Borrowed(ring) => Arc::new(ring.clone()),
+            RingRef::Shared(ring) => ring,
+        };
         Matrix {
-            ring,
-            rows,
-            columns: rows,
-            data,
+            ring: RingRef::Shared(ring),
+            rows: self.rows,
+            columns: self.columns,
+            data: self.data,
         }
     }
 
-    pub fn scale(&self, scalar: F::RingMember) -> Matrix<F> {
-        let mut ans: Matrix<F> = Matrix {
-            ring: self.ring,
+    pub fn scale(&self, scalar: F::RingMember) -> Matrix<'a, F> {
+        Matrix {
+            ring: self.ring.clone(),
             rows: self.rows,
             columns: self.columns,
-            data: vec![vec![self.ring.zero(); self.columns]; self.rows],
-        };
-        for i in 0..self.rows {
-            for j in 0..self.columns {
-                ans.data[i][j] = self.ring.mul(&self.data[i][j], &scalar);
-            }
+            data: self
+                .data
+                .iter()
+                .map(|x| self.ring.mul(x, &scalar))
+                .collect(),
         }
-        ans
     }
 
-    pub fn add(&self, rhs: &Matrix<'a, F>) -> Result<Matrix<'a, F>, Error> {
+    pub fn add(&self, rhs: &Matrix<F>) -> Result<Matrix<'a, F>, Error> {
+        self.checked_add(rhs)
+    }
+
+    pub fn sub(&self, rhs: &Matrix<F>) -> Result<Matrix<'a, F>, Error> {
+        self.checked_sub(rhs)
+    }
+
+    pub fn mul(&self, rhs: &Matrix<F>) -> Result<Matrix<'a, F>, Error> {
+        self.checked_mul(rhs)
+    }
+
+    pub fn checked_add(&self, rhs: &Matrix<F>) -> Result<Matrix<'a, F>, Error> {
         if self.rows != rhs.rows || self.columns != rhs.columns {
             Result::Err(Error::DimensionMismatchForMatrixAddition(
                 self.rows,
@@ -266,22 +846,21 @@ impl<'a, F: Ring> Matrix<'a, F> {
                 rhs.columns,
             ))
         } else {
-            let mut ans: Matrix<F> = Matrix {
-                ring: self.ring,
+            Ok(Matrix {
+                ring: self.ring.clone(),
                 rows: self.rows,
                 columns: self.columns,
-                data: vec![vec![self.ring.zero(); self.columns]; self.rows],
-            };
-            for i in 0..self.rows {
-                for j in 0..self.columns {
-                    ans.data[i][j] = self.ring.add(&self.data[i][j], &rhs.data[i][j]);
-                }
-            }
-            Ok(ans)
+                data: self
+                    .data
+                    .iter()
+                    .zip(rhs.data.iter())
+                    .map(|(x, y)| self.ring.add(x, y))
+                    .collect(),
+            })
         }
     }
 
-    pub fn sub(&self, rhs: &Matrix<F>) -> Result<Matrix<F>, Error> {
+    pub fn checked_sub(&self, rhs: &Matrix<F>) -> Result<Matrix<'a, F>, Error> {
         if self.rows != rhs.rows || self.columns != rhs.columns {
             Result::Err(Error::DimensionMismatchForMatrixAddition(
                 self.rows,
@@ -290,24 +869,22 @@ impl<'a, F: Ring> Matrix<'a, F> {
                 rhs.columns,
             ))
         } else {
-            let mut ans: Matrix<F> = Matrix {
-                ring: self.ring,
+            Ok(Matrix {
+                ring: self.ring.clone(),
                 rows: self.rows,
                 columns: self.columns,
-                data: vec![vec![self.ring.zero(); self.columns]; self.rows],
-            };
-            for i in 0..self.rows {
-                for j in 0..self.columns {
-                    ans.data[i][j] = self
-                        .ring
-                        .add(&self.data[i][j], &self.ring.neg(&rhs.data[i][j]));
-                }
-            }
-            Ok(ans)
+                data: self
+                    .data
+                    .iter()
+                    .zip(rhs.data.iter())
+                    .map(|(x, y)| self.ring.add(x, &self.ring.neg(y)))
+                    .collect(),
+            })
         }
     }
-    //vanila matrix multiplication
-    pub fn mul(&self, rhs: &Matrix<F>) -> Result<Matrix<F>, Error> {
+    //vanila matrix multiplication, with the loops ordered so that both operands
+    //are walked along their rows
+    pub fn checked_mul(&self, rhs: &Matrix<F>) -> Result<Matrix<'a, F>, Error> {
         if self.columns != rhs.rows {
             Result::Err(Error::DimensionMismatchForMatrixMultiplication(
                 self.rows,
@@ -316,105 +893,55 @@ impl<'a, F: Ring> Matrix<'a, F> {
                 rhs.columns,
             ))
         } else {
-            let mut ans: Matrix<F> = Matrix {
-                ring: self.ring,
-                rows: self.rows,
-                columns: rhs.columns,
-                data: vec![vec![self.ring.zero(); rhs.columns]; self.rows],
-            };
-            for i in 0..self.rows {
-                for j in 0..rhs.columns {
-                    for k in 0..self.columns {
-                        let prod = self.ring.mul(&self.data[i][k], &rhs.data[k][j]);
-                        ans.data[i][j] = self.ring.add(&ans.data[i][j], &prod);
+            let mut data = vec![self.ring.zero(); self.rows * rhs.columns];
+            for (i, ans_row) in data.chunks_mut(rhs.columns.max(1)).enumerate() {
+                for (k, lhs_value) in self.row(i).iter().enumerate() {
+                    for (ans, rhs_value) in ans_row.iter_mut().zip(rhs.row(k).iter()) {
+                        *ans = self.ring.add(ans, &self.ring.mul(lhs_value, rhs_value));
                     }
                 }
             }
-            Ok(ans)
+            Ok(Matrix {
+                ring: self.ring.clone(),
+                rows: self.rows,
+                columns: rhs.columns,
+                data,
+            })
         }
     }
     pub fn transpose(&self) -> Matrix<'a, F> {
-        let rows = self.columns;
-        let columns = self.rows;
-        let mut ans: Matrix<F> = Matrix {
-            ring: self.ring,
-            rows,
-            columns,
-            data: vec![vec![self.ring.zero(); columns]; rows],
-        };
-        for j in 0..columns {
-            for i in 0..rows {
-                ans.data[i][j] = self.data[j][i].clone();
-            }
-        }
-        ans
-    }
-}
-
-//Ops implemetations
-impl<'a, F: Ring> Add<&Matrix<'a, F>> for &'a Matrix<'a, F> {
-    type Output = Matrix<'a, F>;
-
-    fn add(self, rhs: &Matrix<'a, F>) -> Matrix<'a, F> {
-        match self.add(rhs) {
-            Ok(result) => result,
-            Err(e) => {
-                panic!("{}", e.to_string());
-            }
+        let mut data = Vec::with_capacity(self.data.len());
+        for j in 0..self.columns {
+            data.extend(self.data.iter().skip(j).step_by(self.columns).cloned());
         }
-    }
-}
-
-impl<'a, F: Ring> Sub<&Matrix<'a, F>> for &'a Matrix<'a, F> {
-    type Output = Matrix<'a, F>;
-
-    fn sub(self, rhs: &Matrix<'a, F>) -> Matrix<'a, F> {
-        match self.sub(rhs) {
-            Ok(result) => result,
-            Err(e) => {
-                panic!("{}", e.to_string());
-            }
+        Matrix {
+            ring: self.ring.clone(),
+            rows: self.columns,
+            columns: self.rows,
+            data,
         }
     }
 }
 
-impl<'a, F: Ring> Mul<&Matrix<'a, F>> for &'a Matrix<'a, F> {
-    type Output = Matrix<'a, F>;
-
-    fn mul(self, rhs: &Matrix<'a, F>) -> Matrix<'a, F> {
-        match self.mul(rhs) {
-            Ok(result) => result,
-            Err(e) => {
-                panic!("{}", e.to_string());
-            }
-        }
+impl<'a, F: Field> Matrix<'a, F> {
+    //self * rhs^-1
+    pub fn checked_div(&self, rhs: &Matrix<F>) -> Result<Matrix<'a, F>, Error> {
+        self.checked_mul(&rhs.inverse()?)
     }
 }
 
-impl<'a, F: Field> Div<&Matrix<'a, F>> for &'a Matrix<'a, F> {
-    type Output = Matrix<'a, F>;
-
-    fn div(self, rhs: &Matrix<F>) -> Matrix<'a, F> {
-        match rhs.inverse() {
-            Ok(inv) => match self.mul(&inv) {
-                Ok(result) => result,
-                Err(e) => {
-                    panic!("{}", e.to_string());
-                }
-            },
-            Err(e) => {
-                panic!("{}", e.to_string());
-            }
-        }
-    }
-}
+//Wrapper for multiplying a matrix by a ring element with the * operator. A bare
+//F::RingMember cannot be used there since it might itself be a matrix.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Scalar<T>(pub T);
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::field::ModularField;
-    use int_ring::I32Ring;
-    use num::BigUint;
+    use crate::binary::{BinaryField, Newable};
+    use crate::field::{F64Field, ModularField};
+    use int_ring::{BigIntRing, I32Ring, I64Ring};
+    use num::{BigInt, BigUint};
     #[test]
     fn test_zero() {
         let ring = I32Ring {};
@@ -439,6 +966,13 @@ mod tests {
         assert_eq!(lhs, res);
     }
 
+    #[test]
+    #[should_panic(expected = "same length")]
+    fn test_ragged_rows() {
+        let ring = I32Ring {};
+        Matrix::new(&ring, vec![vec![1, 2, 3], vec![4, 5]]);
+    }
+
     #[test]
     fn test_add() {
         let ring = I32Ring {};
@@ -534,6 +1068,438 @@ mod tests {
         assert_eq!(identity_matrix, inv.mul(&mat).expect(""));
     }
 
+    #[test]
+    fn test_determinant() {
+        let ring = ModularField::new(BigUint::from(7u64));
+        let n = |x: u64| BigUint::from(x);
+        let mat: Matrix<ModularField> = Matrix::new(
+            &ring,
+            vec![
+                vec![n(0), n(2), n(3)],
+                vec![n(2), n(1), n(3)],
+                vec![n(3), n(1), n(3)],
+            ],
+        );
+        // 0*(3-3) - 2*(6-9) + 3*(2-3) = 3
+        assert_eq!(mat.determinant().unwrap(), BigUint::from(3u64));
+    }
+
+    #[test]
+    fn test_determinant_singular() {
+        let ring = F64Field;
+        let mat = matrix!(&ring, [1.0, 2.0; 2.0, 4.0]);
+        assert_eq!(mat.determinant().unwrap(), 0.0);
+        let rect = matrix!(&ring, [1.0, 2.0]);
+        assert!(rect.determinant().is_err());
+    }
+
+    #[test]
+    fn test_division_free_determinant() {
+        let ring = I64Ring;
+        let mat = matrix!(&ring, [2, -1, 0, 3; 1, 4, 2, 0; 0, 5, -3, 1; 7, 0, 1, 2]);
+        assert_eq!(mat.division_free_determinant().unwrap(), 358);
+        let zero_matrix: Matrix<I64Ring> = Matrix::zero(&ring, 1);
+        assert_eq!(zero_matrix.division_free_determinant().unwrap(), 0);
+
+        let ring = BigIntRing;
+        let mat = matrix!(
+            &ring,
+            [BigInt::from(3), BigInt::from(8); BigInt::from(4), BigInt::from(6)]
+        );
+        assert_eq!(mat.division_free_determinant().unwrap(), BigInt::from(-14));
+    }
+
+    #[test]
+    fn test_rref() {
+        let ring = ModularField::new(BigUint::from(5u64));
+        let n = |x: u64| BigUint::from(x);
+        let mat: Matrix<ModularField> = Matrix::new(
+            &ring,
+            vec![
+                vec![n(1), n(2), n(0), n(3)],
+                vec![n(2), n(4), n(1), n(1)],
+                vec![n(3), n(1), n(1), n(2)],
+            ],
+        );
+        let (rref, pivots) = mat.rref();
+        let exp_res: Matrix<ModularField> = Matrix::new(
+            &ring,
+            vec![
+                vec![n(1), n(2), n(0), n(0)],
+                vec![n(0), n(0), n(1), n(0)],
+                vec![n(0), n(0), n(0), n(1)],
+            ],
+        );
+        assert_eq!(exp_res, rref);
+        assert_eq!(pivots, vec![0, 2, 3]);
+        assert_eq!(mat.rank(), 3);
+        assert_eq!(mat.nullity(), 1);
+    }
+
+    #[test]
+    fn test_rank_deficient() {
+        let ring = F64Field;
+        let mat = matrix!(&ring, [1.0, 2.0, 3.0; 2.0, 4.0, 6.0; 0.0, 0.0, 1.0; 1.0, 2.0, 4.0]);
+        let (rref, pivots) = mat.rref();
+        assert_eq!(pivots, vec![0, 2]);
+        let exp_res = matrix!(&ring, [1.0, 2.0, 0.0; 0.0, 0.0, 1.0; 0.0, 0.0, 0.0; 0.0, 0.0, 0.0]);
+        assert_eq!(exp_res, rref);
+        assert_eq!(mat.rank(), 2);
+        assert_eq!(mat.nullity(), 1);
+    }
+
+    #[test]
+    fn test_solve_unique() {
+        let ring = ModularField::new(BigUint::from(7u64));
+        let n = |x: u64| BigUint::from(x);
+        let mat: Matrix<ModularField> =
+            Matrix::new(&ring, vec![vec![n(1), n(2)], vec![n(2), n(1)]]);
+        let rhs: Matrix<ModularField> =
+            Matrix::new(&ring, vec![vec![n(5), n(1)], vec![n(4), n(0)]]);
+        let solution = mat.solve(&rhs).unwrap();
+        assert_eq!(solution.homogeneous.columns(), 0);
+        assert_eq!(mat.checked_mul(&solution.particular).unwrap(), rhs);
+    }
+
+    #[test]
+    fn test_solve_underdetermined() {
+        let ring = F64Field;
+        let mat = matrix!(&ring, [1.0, 2.0, 1.0; 2.0, 4.0, 0.0]);
+        let rhs = matrix!(&ring, [3.0; 2.0]);
+        let solution = mat.solve(&rhs).unwrap();
+        assert_eq!(solution.particular, matrix!(&ring, [1.0; 0.0; 2.0]));
+        assert_eq!(solution.homogeneous, matrix!(&ring, [-2.0; 1.0; 0.0]));
+        assert_eq!(
+            mat.checked_mul(&solution.homogeneous).unwrap(),
+            matrix!(&ring, [0.0; 0.0])
+        );
+    }
+
+    #[test]
+    fn test_solve_inconsistent() {
+        let ring = F64Field;
+        let mat = matrix!(&ring, [1.0, 2.0; 2.0, 4.0]);
+        let rhs = matrix!(&ring, [1.0; 3.0]);
+        assert!(matches!(
+            mat.solve(&rhs),
+            Err(Error::InconsistentLinearSystem)
+        ));
+        let rhs = matrix!(&ring, [1.0; 3.0; 4.0]);
+        assert!(matches!(
+            mat.solve(&rhs),
+            Err(Error::DimensionMismatchForLinearSystem(2, 2, 3, 1))
+        ));
+    }
+
+    #[test]
+    fn test_rounding_residues() {
+        let ring = F64Field;
+        //0.6 - 3 * 0.2 and 0.9 - 3 * 0.3 are not exactly zero in floating point
+        let mat = matrix!(&ring, [0.1, 0.2; 0.3, 0.6]);
+        let rhs = matrix!(&ring, [0.3; 0.9]);
+        let solution = mat.solve(&rhs).unwrap();
+        assert_eq!(solution.homogeneous.columns(), 1);
+        let product = mat.checked_mul(&solution.particular).unwrap();
+        assert!((product[(0, 0)] - 0.3).abs() < 1e-12);
+        assert!((product[(1, 0)] - 0.9).abs() < 1e-12);
+        let mat = matrix!(&ring, [0.1, 0.2, 0.3; 0.4, 0.5, 0.6; 0.7, 0.8, 0.9]);
+        let (rref, pivots) = mat.rref();
+        assert_eq!(pivots, vec![0, 1]);
+        assert_eq!(rref.row(2), &[0.0, 0.0, 0.0][..]);
+        assert_eq!(rref[(0, 1)], 0.0);
+        assert_eq!(rref[(1, 0)], 0.0);
+    }
+
+    #[test]
+    fn test_kernel_and_image() {
+        let ring = F64Field;
+        let mat = matrix!(&ring, [1.0, 2.0, 3.0; 2.0, 4.0, 6.0; 1.0, 0.0, 1.0]);
+        let kernel = mat.kernel();
+        assert_eq!(kernel, matrix!(&ring, [-1.0; -1.0; 1.0]));
+        assert_eq!(
+            mat.checked_mul(&kernel).unwrap(),
+            matrix!(&ring, [0.0; 0.0; 0.0])
+        );
+        assert_eq!(mat.image(), matrix!(&ring, [1.0, 2.0; 2.0, 4.0; 1.0, 0.0]));
+        let left_kernel = mat.left_kernel();
+        assert_eq!(left_kernel, matrix!(&ring, [-2.0; 1.0; 0.0]));
+        assert_eq!(
+            left_kernel.transpose().checked_mul(&mat).unwrap(),
+            matrix!(&ring, [0.0, 0.0, 0.0])
+        );
+    }
+
+    #[test]
+    fn test_kernel_modular_entries_are_reduced() {
+        let ring = ModularField::new(BigUint::from(7u64));
+        let n = |x: u64| BigUint::from(x);
+        let mat: Matrix<ModularField> =
+            Matrix::new(&ring, vec![vec![n(1), n(2), n(0)], vec![n(2), n(4), n(0)]]);
+        //negating a zero entry of the reduced matrix must give 0 and not the modulus
+        let kernel = mat.kernel();
+        assert_eq!(
+            kernel,
+            Matrix::new(
+                &ring,
+                vec![vec![n(5), n(0)], vec![n(1), n(0)], vec![n(0), n(1)]]
+            )
+        );
+        assert!(kernel.data.iter().all(|x| *x < n(7)));
+        assert_eq!(
+            mat.left_kernel(),
+            Matrix::new(&ring, vec![vec![n(5)], vec![n(1)]])
+        );
+    }
+
+    #[test]
+    fn test_parity_check_matrix() {
+        //generator matrix of the [7, 4] Hamming code, codewords being the rows
+        let field = BinaryField::<u8>::new();
+        let generator = matrix!(&field, [
+            1, 0, 0, 0, 1, 1, 0;
+            0, 1, 0, 0, 1, 0, 1;
+            0, 0, 1, 0, 0, 1, 1;
+            0, 0, 0, 1, 1, 1, 1
+        ]);
+        let parity_check = generator.kernel().transpose();
+        assert_eq!(parity_check.rows(), 3);
+        assert_eq!(parity_check.rank(), 3);
+        assert_eq!(
+            generator.checked_mul(&parity_check.transpose()).unwrap(),
+            Matrix::new(&field, vec![vec![0u8; 3]; 4])
+        );
+    }
+
+    fn hilbert_like(size: usize) -> Matrix<'static, ModularField> {
+        let ring = ModularField::new(BigUint::from(101u64));
+        let data = (0..size)
+            .map(|i| {
+                (0..size)
+                    .map(|j| ring.inv(&BigUint::from((i + j + 1) as u64)).unwrap())
+                    .collect()
+            })
+            .collect();
+        Matrix::new(&ring, data).into_shared()
+    }
+
+    #[test]
+    fn test_shared_ring() {
+        let mat = hilbert_like(3);
+        let inv = mat.inverse().unwrap();
+        let identity = Matrix::new_shared(
+            Arc::new(mat.ring().clone()),
+            vec![
+                vec![
+                    BigUint::from(1u64),
+                    BigUint::from(0u64),
+                    BigUint::from(0u64),
+                ],
+                vec![
+                    BigUint::from(0u64),
+                    BigUint::from(1u64),
+                    BigUint::from(0u64),
+                ],
+                vec![
+                    BigUint::from(0u64),
+                    BigUint::from(0u64),
+                    BigUint::from(1u64),
+                ],
+            ],
+        );
+        assert_eq!(&inv * &mat, identity);
+        //a borrowed ring compares equal to a shared copy of it
+        let ring = ModularField::new(BigUint::from(101u64));
+        assert_eq!(Matrix::one(&ring, 3), identity);
+    }
+
+    #[test]
+    fn test_matrix_across_threads() {
+        let mat = hilbert_like(4);
+        let handle = std::thread::spawn(move || mat.determinant().unwrap());
+        assert_ne!(handle.join().unwrap(), BigUint::from(0u64));
+
+        //zero sized rings can simply be borrowed for 'static
+        let mat: Matrix<'static, I32Ring> = matrix!(&I32Ring, [1, 2; 3, 4]);
+        let handle = std::thread::spawn(move || mat.division_free_determinant().unwrap());
+        assert_eq!(handle.join().unwrap(), -2);
+    }
+
+    #[test]
+    fn test_index_and_set() {
+        let ring = I32Ring;
+        let mut mat = matrix!(&ring, [1, 2, 3; 4, 5, 6]);
+        assert_eq!(mat[(1, 2)], 6);
+        mat[(0, 1)] = 7;
+        mat.set(1, 0, 8);
+        assert_eq!(mat, matrix!(&ring, [1, 7, 3; 8, 5, 6]));
+        mat.row_mut(0)[2] = 9;
+        assert_eq!(mat.row(0), &[1, 7, 9]);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_index_out_of_range() {
+        let ring = I32Ring;
+        let mat = matrix!(&ring, [1, 2; 3, 4]);
+        let _ = mat[(0, 2)];
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let ring = I32Ring;
+        let mut mat = matrix!(&ring, [1, 2, 3; 4, 5, 6]);
+        let column = mat.column(1);
+        assert_eq!(column.len(), 2);
+        assert_eq!(column[1], 5);
+        assert_eq!(column.get(2), None);
+        assert_eq!(column.to_vec(), vec![2, 5]);
+        let rows: Vec<&[i32]> = mat.row_iter().collect();
+        assert_eq!(rows, vec![&[1, 2, 3], &[4, 5, 6]]);
+        let columns: Vec<Vec<i32>> = mat.column_iter().map(|c| c.to_vec()).collect();
+        assert_eq!(columns, vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+        mat.swap_rows(0, 1);
+        mat.swap_columns(0, 2);
+        assert_eq!(mat, matrix!(&ring, [6, 5, 4; 3, 2, 1]));
+    }
+
+    #[test]
+    fn test_characteristic_polynomial() {
+        let ring = BigIntRing;
+        let n = |x: i64| BigInt::from(x);
+        let mat: Matrix<BigIntRing> = Matrix::new(
+            &ring,
+            vec![
+                vec![n(2), n(-1), n(0)],
+                vec![n(1), n(3), n(4)],
+                vec![n(0), n(5), n(-2)],
+            ],
+        );
+        let coeffs = mat.characteristic_polynomial().unwrap();
+        //x^3 - 3x^2 - 23x + 54
+        assert_eq!(coeffs, vec![n(54), n(-23), n(-3), n(1)]);
+        assert_eq!(
+            mat.evaluate_polynomial(&coeffs).unwrap(),
+            Matrix::zero(&ring, 3)
+        );
+        assert_eq!(
+            Matrix::zero(&ring, 0).characteristic_polynomial().unwrap(),
+            vec![n(1)]
+        );
+        assert!(matches!(
+            Matrix::new(&ring, vec![vec![n(1), n(2)]]).characteristic_polynomial(),
+            Err(Error::PolynomialOfRectangularMatrix)
+        ));
+    }
+
+    #[test]
+    fn test_minimal_polynomial() {
+        let ring = ModularField::new(BigUint::from(7u64));
+        let n = |x: u64| BigUint::from(x);
+        let mat: Matrix<ModularField> = Matrix::new(
+            &ring,
+            vec![
+                vec![n(2), n(0), n(0)],
+                vec![n(0), n(3), n(0)],
+                vec![n(0), n(0), n(2)],
+            ],
+        );
+        //(x - 2)(x - 3) = x^2 - 5x + 6
+        let minimal = mat.minimal_polynomial().unwrap();
+        assert_eq!(minimal, vec![n(6), n(2), n(1)]);
+        assert_eq!(
+            mat.evaluate_polynomial(&minimal).unwrap(),
+            Matrix::zero(&ring, 3)
+        );
+        //(x - 2)^2 (x - 3) = x^3 - 7x^2 + 16x - 12
+        assert_eq!(
+            mat.characteristic_polynomial().unwrap(),
+            vec![n(2), n(2), n(0), n(1)]
+        );
+
+        let jordan: Matrix<ModularField> =
+            Matrix::new(&ring, vec![vec![n(2), n(1)], vec![n(0), n(2)]]);
+        assert_eq!(
+            jordan.minimal_polynomial().unwrap(),
+            jordan.characteristic_polynomial().unwrap()
+        );
+        assert_eq!(
+            Matrix::one(&ring, 2).minimal_polynomial().unwrap(),
+            vec![n(6), n(1)]
+        );
+    }
+
+    #[test]
+    fn test_owned_operators() {
+        let ring = I32Ring;
+        let a = matrix!(&ring, [1, 2; 3, 4]);
+        let b = matrix!(&ring, [0, 1; 1, 0]);
+        assert_eq!(a.clone() + b.clone(), matrix!(&ring, [1, 3; 4, 4]));
+        assert_eq!(&a - b.clone(), matrix!(&ring, [1, 1; 2, 4]));
+        assert_eq!(a.clone() * &b, matrix!(&ring, [2, 1; 4, 3]));
+        assert_eq!(-&a, matrix!(&ring, [-1, -2; -3, -4]));
+        assert_eq!(-a.clone() + &a, Matrix::zero(&ring, 2));
+        assert_eq!(&a * Scalar(3), matrix!(&ring, [3, 6; 9, 12]));
+    }
+
+    #[test]
+    fn test_inherent_methods_on_owned_matrices() {
+        //the operator traits are not in scope here, so these are the Result returning methods
+        let ring = I32Ring;
+        let a = matrix!(&ring, [1, 2; 3, 4]);
+        let b = matrix!(&ring, [0, 1; 1, 0]);
+        assert_eq!(a.clone().mul(&b).unwrap(), a.clone() * &b);
+        assert_eq!(a.clone().add(&b).unwrap(), &a + &b);
+        assert_eq!(a.clone().sub(&b).unwrap(), &a - &b);
+        assert!(a.clone().mul(&matrix!(&ring, [1, 2, 3])).is_err());
+    }
+
+    #[test]
+    fn test_assign_operators() {
+        let ring = I32Ring;
+        let mut a = matrix!(&ring, [1, 2; 3, 4]);
+        let b = matrix!(&ring, [0, 1; 1, 0]);
+        a += &b;
+        assert_eq!(a, matrix!(&ring, [1, 3; 4, 4]));
+        a -= b.clone();
+        assert_eq!(a, matrix!(&ring, [1, 2; 3, 4]));
+        a *= &b;
+        assert_eq!(a, matrix!(&ring, [2, 1; 4, 3]));
+        a *= Scalar(2);
+        assert_eq!(a, matrix!(&ring, [4, 2; 8, 6]));
+    }
+
+    #[test]
+    fn test_checked_operations() {
+        let ring = F64Field;
+        let a = matrix!(&ring, [1.0, 2.0; 3.0, 4.0]);
+        let b = matrix!(&ring, [1.0, 2.0, 3.0]);
+        assert!(matches!(
+            a.checked_add(&b),
+            Err(Error::DimensionMismatchForMatrixAddition(2, 2, 1, 3))
+        ));
+        assert!(a.checked_sub(&b).is_err());
+        assert!(a.checked_mul(&b).is_err());
+        assert_eq!(
+            b.checked_mul(&b.transpose()).unwrap(),
+            matrix!(&ring, [14.0])
+        );
+        let singular = matrix!(&ring, [1.0, 2.0; 2.0, 4.0]);
+        assert!(a.checked_div(&singular).is_err());
+        let d = matrix!(&ring, [2.0, 0.0; 0.0, 4.0]);
+        assert_eq!(
+            a.checked_div(&d).unwrap(),
+            matrix!(&ring, [0.5, 0.5; 1.5, 1.0])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "incompatible dimensions")]
+    fn test_add_assign_mismatch() {
+        let ring = I32Ring;
+        let mut a = matrix!(&ring, [1, 2; 3, 4]);
+        a += matrix!(&ring, [1, 2]);
+    }
+
     #[test]
     fn test_matrix_macro() {
         let ring = I32Ring;
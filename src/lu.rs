@@ -0,0 +1,341 @@
+/*
+ * Copyright  2020 Debasish Ray Chawdhuri
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do
+ * so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+ * EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+ * MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT
+ * HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+ * WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+ * DEALINGS IN THE SOFTWARE.
+ */
+use crate::error::Error;
+use crate::pivoting::Pivoting;
+use crate::{Field, Matrix, RingRef};
+
+//PAQ = LU where P and Q are permutation matrices, L is unit lower triangular and U is upper
+//triangular. Q is the identity unless complete pivoting is used. The factorisation is computed
+//once and reused for every right hand side.
+#[derive(PartialEq, Clone)]
+pub struct LuDecomposition<'a, F: Field> {
+    ring: RingRef<'a, F>,
+    pivoting: Pivoting,
+    //row i of PA is row permutation[i] of A
+    permutation: Vec<usize>,
+    //column i of AQ is column column_permutation[i] of A
+    column_permutation: Vec<usize>,
+    lower: Matrix<'a, F>,
+    upper: Matrix<'a, F>,
+    swaps: usize,
+}
+
+impl<'a, F: Field> LuDecomposition<'a, F> {
+    pub fn new(matrix: &Matrix<'a, F>) -> Result<Self, Error> {
+        Self::new_with_pivoting(matrix, matrix.ring.pivoting())
+    }
+
+    pub fn new_with_pivoting(matrix: &Matrix<'a, F>, pivoting: Pivoting) -> Result<Self, Error> {
+        if matrix.rows != matrix.columns {
+            return Err(Error::DecompositionOfRectangularMatrix);
+        }
+        let ring = &*matrix.ring;
+        let n = matrix.rows;
+        let pivoting = pivoting.relative_to(ring, matrix.data.iter());
+        let mut upper = matrix.clone();
+        let mut lower = Matrix::zeros(matrix.ring.clone(), n, n);
+        let mut permutation: Vec<usize> = (0..n).collect();
+        let mut column_permutation: Vec<usize> = (0..n).collect();
+        let mut swaps = 0;
+
+        for i in 0..n {
+            //a column without a pivot only has negligible entries from the diagonal down. They
+            //are cleared, so U stays triangular and the zero on its diagonal makes the
+            //matrix singular.
+            let pivot = match pivoting {
+                Pivoting::Complete { .. } => pivoting.select(
+                    ring,
+                    (i..n)
+                        .flat_map(|r| (i..n).map(move |c| (r, c)))
+                        .map(|(r, c)| ((r, c), upper.at(r, c))),
+                ),
+                _ => pivoting
+                    .select(ring, (i..n).map(|r| (r, upper.at(r, i))))
+                    .map(|r| (r, i)),
+            };
+            let (p, q) = match pivot {
+                Some(pivot) => pivot,
+                None => {
+                    for j in i..n {
+                        *upper.at_mut(j, i) = ring.zero();
+                    }
+                    continue;
+                }
+            };
+            if p != i {
+                upper.swap_rows(i, p);
+                lower.swap_rows(i, p);
+                permutation.swap(i, p);
+                swaps += 1;
+            }
+            if q != i {
+                upper.swap_columns(i, q);
+                column_permutation.swap(i, q);
+                swaps += 1;
+            }
+            let d_inv = ring.inv(upper.at(i, i))?;
+            for j in i + 1..n {
+                if *upper.at(j, i) == ring.zero() {
+                    continue;
+                }
+                //the pivot row is zero left of column i, so the whole row can be updated
+                let mult = ring.mul(upper.at(j, i), &d_inv);
+                upper.add_multiple_of(j, i, &ring.neg(&mult));
+                *upper.at_mut(j, i) = ring.zero();
+                *lower.at_mut(j, i) = mult;
+            }
+        }
+        for i in 0..n {
+            *lower.at_mut(i, i) = ring.one();
+        }
+
+        Ok(LuDecomposition {
+            ring: matrix.ring.clone(),
+            pivoting,
+            permutation,
+            column_permutation,
+            lower,
+            upper,
+            swaps,
+        })
+    }
+
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+
+    pub fn column_permutation(&self) -> &[usize] {
+        &self.column_permutation
+    }
+
+    pub fn lower(&self) -> &Matrix<'a, F> {
+        &self.lower
+    }
+
+    pub fn upper(&self) -> &Matrix<'a, F> {
+        &self.upper
+    }
+
+    pub fn determinant(&self) -> F::RingMember {
+        let mut det = if self.swaps % 2 == 0 {
+            self.ring.one()
+        } else {
+            self.ring.neg(&self.ring.one())
+        };
+        for i in 0..self.upper.rows {
+            det = self.ring.mul(&det, self.upper.at(i, i));
+        }
+        det
+    }
+
+    //Solves A X = B for every column of B by forward substitution with L followed by
+    //back substitution with U, undoing the column permutation at the end
+    pub fn solve(&self, rhs: &Matrix<F>) -> Result<Matrix<'a, F>, Error> {
+        let n = self.upper.rows;
+        if rhs.rows != n {
+            return Err(Error::DimensionMismatchForLinearSystem(
+                n,
+                n,
+                rhs.rows,
+                rhs.columns,
+            ));
+        }
+        let mut diagonal_inv = Vec::with_capacity(n);
+        for i in 0..n {
+            let d = self.upper.at(i, i);
+            if self.pivoting.is_negligible(&*self.ring, d) {
+                return Err(Error::InversionOfNonInvertibleSquareMatrix);
+            }
+            diagonal_inv.push(self.ring.inv(d)?);
+        }
+
+        let mut result = Matrix {
+            ring: self.ring.clone(),
+            rows: n,
+            columns: rhs.columns,
+            data: vec![self.ring.zero(); n * rhs.columns],
+        };
+        for col in 0..rhs.columns {
+            let mut x: Vec<F::RingMember> = self
+                .permutation
+                .iter()
+                .map(|&p| rhs.at(p, col).clone())
+                .collect();
+            for i in 0..n {
+                let mut sum = x[i].clone();
+                for (l, y) in self.lower.row(i)[..i].iter().zip(x.iter()) {
+                    sum = self.ring.add(&sum, &self.ring.neg(&self.ring.mul(l, y)));
+                }
+                x[i] = sum;
+            }
+            for i in (0..n).rev() {
+                let mut sum = x[i].clone();
+                for (u, y) in self.upper.row(i)[i + 1..].iter().zip(x[i + 1..].iter()) {
+                    sum = self.ring.add(&sum, &self.ring.neg(&self.ring.mul(u, y)));
+                }
+                x[i] = self.ring.mul(&sum, &diagonal_inv[i]);
+            }
+            for (&c, value) in self.column_permutation.iter().zip(x) {
+                *result.at_mut(c, col) = value;
+            }
+        }
+        Ok(result)
+    }
+
+    pub fn inverse(&self) -> Result<Matrix<'a, F>, Error> {
+        self.solve(&Matrix::identity(self.ring.clone(), self.upper.rows))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::{F64Field, ModularField};
+    use crate::matrix;
+    use crate::pivoting::DEFAULT_TOLERANCE;
+    use num::BigUint;
+
+    #[test]
+    fn test_lu_factors() {
+        let ring = F64Field;
+        let mat = matrix!(&ring, [0.0, 2.0, 1.0; 1.0, 1.0, 1.0; 2.0, 1.0, 3.0]);
+        let lu = LuDecomposition::new(&mat).unwrap();
+        let permuted = Matrix::new(
+            &ring,
+            lu.permutation()
+                .iter()
+                .map(|&p| mat.row(p).to_vec())
+                .collect(),
+        );
+        assert_eq!(lu.lower().mul(lu.upper()).unwrap(), permuted);
+        assert_eq!(lu.determinant(), -3.0);
+    }
+
+    #[test]
+    fn test_lu_solve_and_inverse() {
+        let ring = ModularField::new(BigUint::from(11u64));
+        let n = |x: u64| BigUint::from(x);
+        let mat: Matrix<ModularField> = Matrix::new(
+            &ring,
+            vec![
+                vec![n(0), n(3), n(1)],
+                vec![n(4), n(1), n(7)],
+                vec![n(2), n(5), n(9)],
+            ],
+        );
+        let lu = LuDecomposition::new(&mat).unwrap();
+        assert_eq!(lu.determinant(), mat.determinant().unwrap());
+
+        let rhs: Matrix<ModularField> = Matrix::new(
+            &ring,
+            vec![vec![n(1), n(0)], vec![n(2), n(5)], vec![n(3), n(10)]],
+        );
+        let x = lu.solve(&rhs).unwrap();
+        assert_eq!(mat.mul(&x).unwrap(), rhs);
+        assert_eq!(lu.inverse().unwrap(), mat.inverse().unwrap());
+    }
+
+    #[test]
+    fn test_lu_small_pivot() {
+        let ring = F64Field;
+        let mat = matrix!(&ring, [1e-20, 1.0; 1.0, 1.0]);
+        let rhs = matrix!(&ring, [1.0; 2.0]);
+        //without pivoting 1 - 1e20 swamps the second row and x[0] comes out as 0
+        let naive = LuDecomposition::new_with_pivoting(&mat, Pivoting::FirstNonZero).unwrap();
+        assert_eq!(naive.solve(&rhs).unwrap().value_at(0, 0), 0.0);
+        let lu = LuDecomposition::new(&mat).unwrap();
+        assert_eq!(lu.permutation(), &[1, 0]);
+        let x = lu.solve(&rhs).unwrap();
+        assert!((x.value_at(0, 0) - 1.0).abs() < 1e-12);
+        assert!((x.value_at(1, 0) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_lu_complete_pivoting() {
+        let ring = F64Field;
+        let mat = matrix!(&ring, [1.0, 2.0, 0.0; 2.0, 8.0, 1.0; 0.5, 1.0, 4.0]);
+        let lu = LuDecomposition::new_with_pivoting(
+            &mat,
+            Pivoting::Complete {
+                tolerance: DEFAULT_TOLERANCE,
+            },
+        )
+        .unwrap();
+        assert_eq!(lu.permutation()[0], 1);
+        assert_eq!(lu.column_permutation()[0], 1);
+        assert!((lu.determinant() - mat.determinant().unwrap()).abs() < 1e-12);
+        let rhs = matrix!(&ring, [3.0; 11.0; 5.5]);
+        let x = lu.solve(&rhs).unwrap();
+        for i in 0..3 {
+            assert!((x.value_at(i, 0) - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_lu_near_singular() {
+        let ring = F64Field;
+        let mat = matrix!(&ring, [1.0, 1.0; 1.0, 1.0 + 1e-300]);
+        assert!(LuDecomposition::new(&mat).unwrap().inverse().is_err());
+        assert!(mat.inverse().is_err());
+    }
+
+    #[test]
+    fn test_lu_tiny_scale() {
+        let ring = F64Field;
+        let mat = matrix!(&ring, [1e-13, 0.0; 0.0, 1e-13]);
+        let lu = LuDecomposition::new(&mat).unwrap();
+        assert!((lu.determinant() - 1e-26).abs() < 1e-38);
+        let inverse = lu.inverse().unwrap();
+        assert!((inverse.value_at(0, 0) - 1e13).abs() < 1e-2);
+        assert_eq!(mat.inverse().unwrap(), inverse);
+        assert_eq!(mat.determinant().unwrap(), 1e-26);
+        assert_eq!(mat.rank(), 2);
+    }
+
+    #[test]
+    fn test_lu_residue_is_cleared() {
+        let ring = F64Field;
+        //the second column is 2 times the first up to rounding noise
+        let mat = matrix!(&ring, [1.0, 2.0, 0.0; 3.0, 6.0 + 1e-15, 1.0; 2.0, 4.0, 5.0]);
+        let lu = LuDecomposition::new(&mat).unwrap();
+        let upper = lu.upper();
+        for i in 0..3 {
+            for j in 0..i {
+                assert_eq!(upper.value_at(i, j), 0.0);
+            }
+        }
+        assert_eq!(upper.value_at(1, 1), 0.0);
+        assert_eq!(lu.determinant(), 0.0);
+        assert!(lu.inverse().is_err());
+    }
+
+    #[test]
+    fn test_lu_singular() {
+        let ring = F64Field;
+        let mat = matrix!(&ring, [1.0, 2.0; 2.0, 4.0]);
+        let lu = LuDecomposition::new(&mat).unwrap();
+        assert_eq!(lu.determinant(), 0.0);
+        assert!(lu.inverse().is_err());
+        assert!(LuDecomposition::new(&matrix!(&ring, [1.0, 2.0])).is_err());
+    }
+}
@@ -0,0 +1,423 @@
+/*
+ * Copyright  2020 Debasish Ray Chawdhuri
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do
+ * so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+ * EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+ * MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT
+ * HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+ * WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+ * DEALINGS IN THE SOFTWARE.
+ */
+use crate::error::Error;
+use crate::euclidian_domain::EuclidianDomain;
+use crate::Matrix;
+
+impl<'a, R: EuclidianDomain> Matrix<'a, R> {
+    //Replaces rows r1 and r2 by a * r1 + b * r2 and c * r1 + d * r2. The transform is
+    //unimodular as long as ad - bc is a unit.
+    fn combine_rows(&mut self, r1: usize, r2: usize, coeffs: [&R::RingMember; 4]) {
+        let ring = &*self.ring;
+        let [a, b, c, d] = coeffs;
+        let (new1, new2): (Vec<R::RingMember>, Vec<R::RingMember>) = self
+            .row(r1)
+            .iter()
+            .zip(self.row(r2).iter())
+            .map(|(x, y)| {
+                (
+                    ring.add(&ring.mul(a, x), &ring.mul(b, y)),
+                    ring.add(&ring.mul(c, x), &ring.mul(d, y)),
+                )
+            })
+            .unzip();
+        self.row_mut(r1).clone_from_slice(&new1);
+        self.row_mut(r2).clone_from_slice(&new2);
+    }
+
+    //Column version of combine_rows, c1 and c2 become a * c1 + b * c2 and c * c1 + d * c2
+    fn combine_columns(&mut self, c1: usize, c2: usize, coeffs: [&R::RingMember; 4]) {
+        let [a, b, c, d] = coeffs;
+        for i in 0..self.rows {
+            let x = self.at(i, c1).clone();
+            let y = self.at(i, c2).clone();
+            let ring = &*self.ring;
+            let new1 = ring.add(&ring.mul(a, &x), &ring.mul(b, &y));
+            let new2 = ring.add(&ring.mul(c, &x), &ring.mul(d, &y));
+            *self.at_mut(i, c1) = new1;
+            *self.at_mut(i, c2) = new2;
+        }
+    }
+
+    //exact quotient, the caller knows that divisor divides value
+    fn exact_quotient(
+        &self,
+        value: &R::RingMember,
+        divisor: &R::RingMember,
+    ) -> Result<R::RingMember, Error> {
+        Ok(self.ring.division_algorithm(value, divisor)?.quotient)
+    }
+
+    //Quotient q such that value - q * divisor is the canonical remainder modulo a normalised
+    //divisor. Division in a ring like BigIntRing truncates, leaving a remainder in
+    //(-divisor, 0) for negative values; adding the divisor once more brings it into
+    //[0, divisor). Remainders that would not shrink back below the divisor are kept.
+    fn reducing_quotient(
+        &self,
+        value: &R::RingMember,
+        divisor: &R::RingMember,
+    ) -> Result<R::RingMember, Error> {
+        let ring = &*self.ring;
+        let division = ring.division_algorithm(value, divisor)?;
+        let remainder = division.remainder;
+        if remainder == ring.zero() || ring.normalization_unit(&remainder) == ring.one() {
+            return Ok(division.quotient);
+        }
+        let shifted = ring.add(&remainder, divisor);
+        if ring.normalization_unit(&shifted) == ring.one()
+            && ring.division_algorithm(&shifted, divisor)?.quotient == ring.zero()
+        {
+            Ok(ring.add(&division.quotient, &ring.neg(&ring.one())))
+        } else {
+            Ok(division.quotient)
+        }
+    }
+
+    //Makes the entry at (row, col) the gcd of itself and the entry at (other, col), which
+    //becomes zero. The same row operations are applied to transform.
+    fn gcd_rows(
+        &mut self,
+        transform: &mut Matrix<R>,
+        row: usize,
+        other: usize,
+        col: usize,
+    ) -> Result<(), Error> {
+        let ring = &*self.ring;
+        let a = self.at(row, col).clone();
+        let b = self.at(other, col).clone();
+        let euclid = ring.extended_euclid(&a, &b)?;
+        let a_g = self.exact_quotient(&a, &euclid.gcd)?;
+        let minus_b_g = ring.neg(&self.exact_quotient(&b, &euclid.gcd)?);
+        let coeffs = [&euclid.x, &euclid.y, &minus_b_g, &a_g];
+        self.combine_rows(row, other, coeffs);
+        transform.combine_rows(row, other, coeffs);
+        Ok(())
+    }
+
+    //Column version of gcd_rows, the entry at (row, other) becomes zero
+    fn gcd_columns(
+        &mut self,
+        transform: &mut Matrix<R>,
+        col: usize,
+        other: usize,
+        row: usize,
+    ) -> Result<(), Error> {
+        let ring = &*self.ring;
+        let a = self.at(row, col).clone();
+        let b = self.at(row, other).clone();
+        let euclid = ring.extended_euclid(&a, &b)?;
+        let a_g = self.exact_quotient(&a, &euclid.gcd)?;
+        let minus_b_g = ring.neg(&self.exact_quotient(&b, &euclid.gcd)?);
+        let coeffs = [&euclid.x, &euclid.y, &minus_b_g, &a_g];
+        self.combine_columns(col, other, coeffs);
+        transform.combine_columns(col, other, coeffs);
+        Ok(())
+    }
+
+    //Row style Hermite normal form. Returns (H, U) with U unimodular and U * A = H, where H
+    //is in row echelon form, every pivot is normalised with EuclidianDomain::normalization_unit
+    //and the entries above a pivot are remainders modulo that pivot.
+    pub fn hermite_normal_form(&self) -> Result<(Matrix<'a, R>, Matrix<'a, R>), Error> {
+        let ring = &*self.ring;
+        let mut h = self.clone();
+        let mut u = Matrix::identity(self.ring.clone(), self.rows);
+        let mut r = 0;
+        for c in 0..self.columns {
+            if r == self.rows {
+                break;
+            }
+            for i in r + 1..self.rows {
+                if *h.at(i, c) == ring.zero() {
+                    continue;
+                }
+                if *h.at(r, c) == ring.zero() {
+                    h.swap_rows(r, i);
+                    u.swap_rows(r, i);
+                } else {
+                    h.gcd_rows(&mut u, r, i, c)?;
+                }
+            }
+            if *h.at(r, c) == ring.zero() {
+                continue;
+            }
+            let unit = ring.normalization_unit(h.at(r, c));
+            h.scale_row(r, &unit);
+            u.scale_row(r, &unit);
+            for i in 0..r {
+                let quotient = h.reducing_quotient(h.at(i, c), h.at(r, c))?;
+                let factor = ring.neg(&quotient);
+                h.add_multiple_of(i, r, &factor);
+                u.add_multiple_of(i, r, &factor);
+            }
+            r += 1;
+        }
+        Ok((h, u))
+    }
+}
+
+//U * A * V = D where U and V are unimodular and D is diagonal with d_1 | d_2 | ... | d_r
+//followed by zeros
+pub struct SmithNormalForm<'a, R: EuclidianDomain> {
+    pub d: Matrix<'a, R>,
+    pub u: Matrix<'a, R>,
+    pub v: Matrix<'a, R>,
+    //the non-zero diagonal entries of D, normalised with EuclidianDomain::normalization_unit
+    pub invariant_factors: Vec<R::RingMember>,
+}
+
+impl<'a, R: EuclidianDomain> Matrix<'a, R> {
+    pub fn smith_normal_form(&self) -> Result<SmithNormalForm<'a, R>, Error> {
+        let ring = &*self.ring;
+        let mut d = self.clone();
+        let mut u = Matrix::identity(self.ring.clone(), self.rows);
+        let mut v = Matrix::identity(self.ring.clone(), self.columns);
+        let mut invariant_factors = vec![];
+        for t in 0..self.rows.min(self.columns) {
+            let pivot = (t..self.rows)
+                .flat_map(|i| (t..self.columns).map(move |j| (i, j)))
+                .find(|&(i, j)| *d.at(i, j) != ring.zero());
+            let (p, q) = match pivot {
+                Some(pivot) => pivot,
+                None => break,
+            };
+            d.swap_rows(t, p);
+            u.swap_rows(t, p);
+            d.swap_columns(t, q);
+            v.swap_columns(t, q);
+
+            loop {
+                //every gcd step replaces the pivot by a proper divisor or leaves it alone, so
+                //this settles after finitely many rounds
+                for i in t + 1..self.rows {
+                    if *d.at(i, t) != ring.zero() {
+                        d.gcd_rows(&mut u, t, i, t)?;
+                    }
+                }
+                for j in t + 1..self.columns {
+                    if *d.at(t, j) != ring.zero() {
+                        d.gcd_columns(&mut v, t, j, t)?;
+                    }
+                }
+                if (t + 1..self.rows).any(|i| *d.at(i, t) != ring.zero()) {
+                    continue;
+                }
+                //the pivot has to divide everything left in the lower right block. If it does
+                //not, adding the offending row brings that entry into row t for the next round.
+                let mut offending = None;
+                for i in t + 1..self.rows {
+                    for j in t + 1..self.columns {
+                        let remainder = ring.division_algorithm(d.at(i, j), d.at(t, t))?.remainder;
+                        if remainder != ring.zero() {
+                            offending = Some(i);
+                            break;
+                        }
+                    }
+                    if offending.is_some() {
+                        break;
+                    }
+                }
+                match offending {
+                    Some(i) => {
+                        d.add_multiple_of(t, i, &ring.one());
+                        u.add_multiple_of(t, i, &ring.one());
+                    }
+                    None => break,
+                }
+            }
+            let unit = ring.normalization_unit(d.at(t, t));
+            d.scale_row(t, &unit);
+            u.scale_row(t, &unit);
+            invariant_factors.push(d.at(t, t).clone());
+        }
+        Ok(SmithNormalForm {
+            d,
+            u,
+            v,
+            invariant_factors,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binary::BinaryRing;
+    use crate::int_ring::BigIntRing;
+    use crate::Ring;
+    use num::BigInt;
+    use proptest::prelude::*;
+
+    fn big_matrix(ring: &BigIntRing, rows: Vec<Vec<i64>>) -> Matrix<'_, BigIntRing> {
+        Matrix::new(
+            ring,
+            rows.into_iter()
+                .map(|row| row.into_iter().map(BigInt::from).collect())
+                .collect(),
+        )
+    }
+
+    fn is_unit(ring: &BigIntRing, value: &BigInt) -> bool {
+        *value == ring.one() || *value == ring.neg(&ring.one())
+    }
+
+    fn check_hermite_form(mat: &Matrix<BigIntRing>) {
+        let ring = BigIntRing;
+        let (h, u) = mat.hermite_normal_form().unwrap();
+        assert_eq!(u.checked_mul(mat).unwrap(), h);
+        assert!(is_unit(&ring, &u.division_free_determinant().unwrap()));
+        let mut last_pivot: Option<usize> = None;
+        for i in 0..h.rows() {
+            match h.row(i).iter().position(|x| *x != ring.zero()) {
+                Some(p) => {
+                    assert!(last_pivot.map_or(true, |l| l < p));
+                    assert!(h[(i, p)] > ring.zero());
+                    for k in 0..i {
+                        assert!(h[(k, p)] >= ring.zero() && h[(k, p)] < h[(i, p)]);
+                    }
+                    last_pivot = Some(p);
+                }
+                None => last_pivot = Some(h.columns()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_hermite_normal_form() {
+        let ring = BigIntRing;
+        let mat = big_matrix(
+            &ring,
+            vec![vec![2, 3, 6, 2], vec![5, 6, 1, 6], vec![8, 3, 1, 1]],
+        );
+        let (h, _) = mat.hermite_normal_form().unwrap();
+        assert_eq!(
+            h,
+            big_matrix(
+                &ring,
+                vec![vec![1, 0, 50, -11], vec![0, 3, 28, -2], vec![0, 0, 61, -13]]
+            )
+        );
+        check_hermite_form(&mat);
+        check_hermite_form(&big_matrix(
+            &ring,
+            vec![vec![0, 0], vec![0, -4], vec![0, 6]],
+        ));
+    }
+
+    #[test]
+    fn test_hermite_normal_form_binary_polynomials() {
+        let ring = BinaryRing::<u8>::new();
+        //x^2 + 1 and x + 1 have gcd x + 1
+        let mat: Matrix<BinaryRing<u8>> = Matrix::new(
+            &ring,
+            vec![vec![vec![0b101], vec![0b1]], vec![vec![0b11], vec![0b10]]],
+        );
+        let (h, u) = mat.hermite_normal_form().unwrap();
+        assert_eq!(u.checked_mul(&mat).unwrap(), h);
+        assert_eq!(h[(0, 0)], vec![0b11]);
+        assert_eq!(h[(1, 0)], ring.zero());
+        assert!(ring.degree(&h[(0, 1)]) < ring.degree(&h[(1, 1)]));
+        assert_eq!(u.division_free_determinant().unwrap(), ring.one());
+    }
+
+    fn check_smith_form(mat: &Matrix<BigIntRing>) -> Vec<BigInt> {
+        let ring = BigIntRing;
+        let snf = mat.smith_normal_form().unwrap();
+        let product = snf.u.checked_mul(mat).unwrap().checked_mul(&snf.v).unwrap();
+        assert_eq!(product, snf.d);
+        assert!(is_unit(&ring, &snf.u.division_free_determinant().unwrap()));
+        assert!(is_unit(&ring, &snf.v.division_free_determinant().unwrap()));
+        for i in 0..snf.d.rows() {
+            for j in 0..snf.d.columns() {
+                let expected = if i == j && i < snf.invariant_factors.len() {
+                    snf.invariant_factors[i].clone()
+                } else {
+                    ring.zero()
+                };
+                assert_eq!(snf.d[(i, j)], expected);
+            }
+        }
+        for pair in snf.invariant_factors.windows(2) {
+            assert!(pair[0] > ring.zero());
+            let division = ring.division_algorithm(&pair[1], &pair[0]).unwrap();
+            assert_eq!(division.remainder, ring.zero());
+        }
+        snf.invariant_factors
+    }
+
+    #[test]
+    fn test_smith_normal_form() {
+        let ring = BigIntRing;
+        let mat = big_matrix(
+            &ring,
+            vec![vec![2, 4, 4], vec![-6, 6, 12], vec![10, -4, -16]],
+        );
+        let factors = check_smith_form(&mat);
+        assert_eq!(
+            factors,
+            vec![BigInt::from(2), BigInt::from(6), BigInt::from(12)]
+        );
+
+        //Z^3 / <(2, 0, 0), (0, 3, 0)> is Z/1 x Z/6 x Z
+        let relations = big_matrix(&ring, vec![vec![2, 0, 0], vec![0, 3, 0], vec![0, 0, 0]]);
+        assert_eq!(
+            check_smith_form(&relations),
+            vec![BigInt::from(1), BigInt::from(6)]
+        );
+        assert!(check_smith_form(&big_matrix(&ring, vec![vec![0, 0, 0]])).is_empty());
+    }
+
+    #[test]
+    fn test_smith_normal_form_binary_polynomials() {
+        let ring = BinaryRing::<u8>::new();
+        //diag(x, x + 1) has invariant factors 1 and x^2 + x
+        let mat: Matrix<BinaryRing<u8>> = Matrix::new(
+            &ring,
+            vec![vec![vec![0b10], vec![]], vec![vec![], vec![0b11]]],
+        );
+        let snf = mat.smith_normal_form().unwrap();
+        assert_eq!(snf.invariant_factors, vec![vec![0b1], vec![0b110]]);
+        let product = snf
+            .u
+            .checked_mul(&mat)
+            .unwrap()
+            .checked_mul(&snf.v)
+            .unwrap();
+        assert_eq!(product, snf.d);
+    }
+
+    proptest! {
+        #[test]
+        fn test_hermite_normal_form_random(values in proptest::collection::vec(-20i64..20, 12)) {
+            let ring = BigIntRing;
+            let rows = values.chunks(4).map(|row| row.to_vec()).collect();
+            check_hermite_form(&big_matrix(&ring, rows));
+        }
+
+        #[test]
+        fn test_smith_normal_form_random(values in proptest::collection::vec(-20i64..20, 12)) {
+            let ring = BigIntRing;
+            let rows = values.chunks(3).map(|row| row.to_vec()).collect();
+            check_smith_form(&big_matrix(&ring, rows));
+        }
+    }
+}
@@ -0,0 +1,180 @@
+/*
+ * Copyright  2020 Debasish Ray Chawdhuri
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do
+ * so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+ * EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+ * MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT
+ * HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+ * WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+ * DEALINGS IN THE SOFTWARE.
+ */
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::error::Error;
+use crate::{Field, Matrix, Ring, Scalar};
+
+//Ops implemetations
+macro_rules! impl_matrix_op {
+    ($op:ident, $method:ident, $checked:ident, $bound:ident) => {
+        impl<'a, F: $bound> $op<&Matrix<'a, F>> for &Matrix<'a, F> {
+            type Output = Matrix<'a, F>;
+
+            fn $method(self, rhs: &Matrix<'a, F>) -> Matrix<'a, F> {
+                match Matrix::$checked(self, rhs) {
+                    Ok(result) => result,
+                    Err(e) => {
+                        panic!("{}", e.to_string());
+                    }
+                }
+            }
+        }
+
+        impl<'a, F: $bound> $op<Matrix<'a, F>> for &Matrix<'a, F> {
+            type Output = Matrix<'a, F>;
+
+            fn $method(self, rhs: Matrix<'a, F>) -> Matrix<'a, F> {
+                $op::$method(self, &rhs)
+            }
+        }
+
+        impl<'a, F: $bound> $op<&Matrix<'a, F>> for Matrix<'a, F> {
+            type Output = Matrix<'a, F>;
+
+            fn $method(self, rhs: &Matrix<'a, F>) -> Matrix<'a, F> {
+                $op::$method(&self, rhs)
+            }
+        }
+
+        impl<'a, F: $bound> $op<Matrix<'a, F>> for Matrix<'a, F> {
+            type Output = Matrix<'a, F>;
+
+            fn $method(self, rhs: Matrix<'a, F>) -> Matrix<'a, F> {
+                $op::$method(&self, &rhs)
+            }
+        }
+    };
+}
+
+impl_matrix_op!(Add, add, checked_add, Ring);
+impl_matrix_op!(Sub, sub, checked_sub, Ring);
+impl_matrix_op!(Mul, mul, checked_mul, Ring);
+impl_matrix_op!(Div, div, checked_div, Field);
+
+impl<'a, F: Ring> Neg for &Matrix<'a, F> {
+    type Output = Matrix<'a, F>;
+
+    fn neg(self) -> Matrix<'a, F> {
+        Matrix {
+            ring: self.ring.clone(),
+            rows: self.rows,
+            columns: self.columns,
+            data: self.data.iter().map(|x| self.ring.neg(x)).collect(),
+        }
+    }
+}
+
+impl<'a, F: Ring> Neg for Matrix<'a, F> {
+    type Output = Matrix<'a, F>;
+
+    fn neg(mut self) -> Matrix<'a, F> {
+        for value in self.data.iter_mut() {
+            *value = self.ring.neg(value);
+        }
+        self
+    }
+}
+
+impl<'a, F: Ring> Mul<Scalar<F::RingMember>> for &Matrix<'a, F> {
+    type Output = Matrix<'a, F>;
+
+    fn mul(self, rhs: Scalar<F::RingMember>) -> Matrix<'a, F> {
+        self.scale(rhs.0)
+    }
+}
+
+impl<'a, F: Ring> Mul<Scalar<F::RingMember>> for Matrix<'a, F> {
+    type Output = Matrix<'a, F>;
+
+    fn mul(mut self, rhs: Scalar<F::RingMember>) -> Matrix<'a, F> {
+        self *= rhs;
+        self
+    }
+}
+
+impl<'a, F: Ring> AddAssign<&Matrix<'a, F>> for Matrix<'a, F> {
+    fn add_assign(&mut self, rhs: &Matrix<'a, F>) {
+        if self.rows != rhs.rows || self.columns != rhs.columns {
+            let e = Error::DimensionMismatchForMatrixAddition(
+                self.rows,
+                self.columns,
+                rhs.rows,
+                rhs.columns,
+            );
+            panic!("{}", e.to_string());
+        }
+        for (x, y) in self.data.iter_mut().zip(rhs.data.iter()) {
+            *x = self.ring.add(x, y);
+        }
+    }
+}
+
+impl<'a, F: Ring> AddAssign<Matrix<'a, F>> for Matrix<'a, F> {
+    fn add_assign(&mut self, rhs: Matrix<'a, F>) {
+        *self += &rhs;
+    }
+}
+
+impl<'a, F: Ring> SubAssign<&Matrix<'a, F>> for Matrix<'a, F> {
+    fn sub_assign(&mut self, rhs: &Matrix<'a, F>) {
+        if self.rows != rhs.rows || self.columns != rhs.columns {
+            let e = Error::DimensionMismatchForMatrixAddition(
+                self.rows,
+                self.columns,
+                rhs.rows,
+                rhs.columns,
+            );
+            panic!("{}", e.to_string());
+        }
+        for (x, y) in self.data.iter_mut().zip(rhs.data.iter()) {
+            *x = self.ring.add(x, &self.ring.neg(y));
+        }
+    }
+}
+
+impl<'a, F: Ring> SubAssign<Matrix<'a, F>> for Matrix<'a, F> {
+    fn sub_assign(&mut self, rhs: Matrix<'a, F>) {
+        *self -= &rhs;
+    }
+}
+
+impl<'a, F: Ring> MulAssign<&Matrix<'a, F>> for Matrix<'a, F> {
+    fn mul_assign(&mut self, rhs: &Matrix<'a, F>) {
+        *self = &*self * rhs;
+    }
+}
+
+impl<'a, F: Ring> MulAssign<Matrix<'a, F>> for Matrix<'a, F> {
+    fn mul_assign(&mut self, rhs: Matrix<'a, F>) {
+        *self = &*self * &rhs;
+    }
+}
+
+impl<'a, F: Ring> MulAssign<Scalar<F::RingMember>> for Matrix<'a, F> {
+    fn mul_assign(&mut self, rhs: Scalar<F::RingMember>) {
+        for value in self.data.iter_mut() {
+            *value = self.ring.mul(value, &rhs.0);
+        }
+    }
+}
@@ -0,0 +1,147 @@
+/*
+ * Copyright  2020 Debasish Ray Chawdhuri
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do
+ * so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+ * EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+ * MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT
+ * HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+ * WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+ * DEALINGS IN THE SOFTWARE.
+ */
+use crate::Field;
+
+//Tolerance used by the floating point fields. The elimination routines scale it by the largest
+//magnitude in the matrix, and pivot candidates whose magnitude is not above the scaled value
+//are treated as zero.
+pub const DEFAULT_TOLERANCE: f64 = 1e-12;
+
+//How elimination chooses its pivot. Every field picks a default through Field::pivoting,
+//which the elimination routines of Matrix use.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Pivoting {
+    //first entry that is not exactly zero, which is all an exact field needs
+    FirstNonZero,
+    //entry of largest magnitude in the pivot column
+    Partial { tolerance: f64 },
+    //entry of largest magnitude in the whole remaining submatrix, swapping columns too. Only
+    //LuDecomposition swaps columns; inverse, determinant, rref and solve of Matrix need the
+    //pivot in a fixed column and treat this as Partial.
+    Complete { tolerance: f64 },
+}
+
+impl Pivoting {
+    pub fn is_negligible<F: Field>(&self, ring: &F, value: &F::RingMember) -> bool {
+        match self {
+            Pivoting::FirstNonZero => *value == ring.zero(),
+            Pivoting::Partial { tolerance } | Pivoting::Complete { tolerance } => {
+                ring.magnitude(value) <= *tolerance
+            }
+        }
+    }
+
+    //Same strategy with the tolerance taken relative to the largest magnitude among values, so
+    //that a well conditioned matrix is not singular just because its entries are small.
+    //Computed once per elimination from the entries of the matrix.
+    pub fn relative_to<'v, F, I>(&self, ring: &F, values: I) -> Pivoting
+    where
+        F: Field,
+        F::RingMember: 'v,
+        I: Iterator<Item = &'v F::RingMember>,
+    {
+        let scale = || values.map(|v| ring.magnitude(v)).fold(0.0, f64::max);
+        match self {
+            Pivoting::FirstNonZero => Pivoting::FirstNonZero,
+            Pivoting::Partial { tolerance } => Pivoting::Partial {
+                tolerance: tolerance * scale(),
+            },
+            Pivoting::Complete { tolerance } => Pivoting::Complete {
+                tolerance: tolerance * scale(),
+            },
+        }
+    }
+
+    //Picks the key of the best pivot among the candidates, or None if all of them are negligible.
+    //Ties go to the candidate that comes first.
+    pub fn select<'v, F, K, I>(&self, ring: &F, candidates: I) -> Option<K>
+    where
+        F: Field,
+        F::RingMember: 'v,
+        I: Iterator<Item = (K, &'v F::RingMember)>,
+    {
+        let mut candidates = candidates.filter(|(_, value)| !self.is_negligible(ring, value));
+        match self {
+            Pivoting::FirstNonZero => candidates.next().map(|(key, _)| key),
+            Pivoting::Partial { .. } | Pivoting::Complete { .. } => {
+                let mut best: Option<(K, f64)> = None;
+                for (key, value) in candidates {
+                    let magnitude = ring.magnitude(value);
+                    match best {
+                        Some((_, m)) if m >= magnitude => {}
+                        _ => best = Some((key, magnitude)),
+                    }
+                }
+                best.map(|(key, _)| key)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::{ComplexField, F64Field, ModularField};
+    use num::BigUint;
+    use num_complex::Complex;
+
+    #[test]
+    fn test_select() {
+        let values = [0.0, 1e-300, -3.0, 2.0, 3.0];
+        let candidates = || values.iter().enumerate();
+        let ring = F64Field;
+        assert_eq!(Pivoting::FirstNonZero.select(&ring, candidates()), Some(1));
+        let partial = Pivoting::Partial {
+            tolerance: DEFAULT_TOLERANCE,
+        };
+        assert_eq!(partial.select(&ring, candidates()), Some(2));
+        assert_eq!(partial.select(&ring, candidates().take(2)), None);
+    }
+
+    #[test]
+    fn test_relative_to() {
+        let ring = F64Field;
+        let values = [1e-13, -4e-13, 0.0];
+        let partial = Pivoting::Partial { tolerance: 1e-12 };
+        assert_eq!(
+            partial.relative_to(&ring, values.iter()),
+            Pivoting::Partial { tolerance: 4e-25 }
+        );
+        let relative = partial.relative_to(&ring, values.iter());
+        assert_eq!(relative.select(&ring, values.iter().enumerate()), Some(1));
+        assert_eq!(partial.select(&ring, values.iter().enumerate()), None);
+        assert_eq!(
+            Pivoting::FirstNonZero.relative_to(&ring, values.iter()),
+            Pivoting::FirstNonZero
+        );
+    }
+
+    #[test]
+    fn test_default_pivoting() {
+        let modular = ModularField::new(BigUint::from(7u64));
+        assert_eq!(modular.pivoting(), Pivoting::FirstNonZero);
+        assert_eq!(modular.magnitude(&BigUint::from(6u64)), 1.0);
+        assert!(matches!(F64Field.pivoting(), Pivoting::Partial { .. }));
+        assert_eq!(ComplexField.magnitude(&Complex::new(3.0, -4.0)), 5.0);
+    }
+}
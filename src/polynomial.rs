@@ -0,0 +1,594 @@
+/*
+ * Copyright  2020 Debasish Ray Chawdhuri
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do
+ * so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+ * EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+ * MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT
+ * HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+ * WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+ * DEALINGS IN THE SOFTWARE.
+ */
+use crate::error::Error;
+use crate::euclidian_domain::{DivisionAlgorithmResult, EuclidianDomain};
+use crate::{Field, Ring};
+use num_bigint::BigUint;
+use std::fmt::Debug;
+
+//Coefficients are stored lowest degree first and never end in a zero, so the zero
+//polynomial has no coefficients at all
+pub struct Polynomial<T>
+where
+    T: Field,
+{
+    coeffs: Vec<T::RingMember>,
+}
+
+impl<T: Field> Clone for Polynomial<T> {
+    fn clone(&self) -> Self {
+        Polynomial {
+            coeffs: self.coeffs.clone(),
+        }
+    }
+}
+
+impl<T: Field> PartialEq for Polynomial<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.coeffs == other.coeffs
+    }
+}
+
+impl<T: Field> Debug for Polynomial<T>
+where
+    T::RingMember: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Polynomial")
+            .field("coeffs", &self.coeffs)
+            .finish()
+    }
+}
+
+impl<T: Field> Polynomial<T> {
+    pub fn coefficients(&self) -> &[T::RingMember] {
+        &self.coeffs
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    //None for the zero polynomial
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    pub fn leading_coefficient(&self) -> Option<&T::RingMember> {
+        self.coeffs.last()
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct PolynomialRing<'a, T: Field> {
+    underlying_field: &'a T,
+}
+
+impl<'a, T: Field> PolynomialRing<'a, T> {
+    pub fn new(underlying_field: &'a T) -> Self {
+        PolynomialRing { underlying_field }
+    }
+
+    pub fn underlying_field(&self) -> &'a T {
+        self.underlying_field
+    }
+
+    //Builds a polynomial from coefficients given lowest degree first
+    pub fn polynomial(&self, coeffs: Vec<T::RingMember>) -> Polynomial<T> {
+        let mut coeffs = coeffs;
+        self.normalize(&mut coeffs);
+        Polynomial { coeffs }
+    }
+
+    //coeff * x^degree
+    pub fn monomial(&self, coeff: T::RingMember, degree: usize) -> Polynomial<T> {
+        let mut coeffs = vec![self.underlying_field.zero(); degree];
+        coeffs.push(coeff);
+        self.polynomial(coeffs)
+    }
+
+    fn normalize(&self, coeffs: &mut Vec<T::RingMember>) {
+        let zero = self.underlying_field.zero();
+        while coeffs.last() == Some(&zero) {
+            coeffs.pop();
+        }
+    }
+
+    //p(x) by Horner's rule
+    pub fn evaluate(&self, p: &Polynomial<T>, x: &T::RingMember) -> T::RingMember {
+        let field = self.underlying_field;
+        p.coeffs
+            .iter()
+            .rev()
+            .fold(field.zero(), |acc, c| field.add(&field.mul(&acc, x), c))
+    }
+
+    //p multiplied by the inverse of its leading coefficient
+    pub fn monic(&self, p: &Polynomial<T>) -> Polynomial<T> {
+        match p.leading_coefficient() {
+            Some(lc) => self.scale(p, &self.underlying_field.inv(lc).unwrap()),
+            None => p.clone(),
+        }
+    }
+
+    pub fn scale(&self, p: &Polynomial<T>, scalar: &T::RingMember) -> Polynomial<T> {
+        let field = self.underlying_field;
+        self.polynomial(p.coeffs.iter().map(|c| field.mul(scalar, c)).collect())
+    }
+
+    pub fn derivative(&self, p: &Polynomial<T>) -> Polynomial<T> {
+        let field = self.underlying_field;
+        let mut n = field.zero();
+        let mut coeffs = Vec::with_capacity(p.coeffs.len());
+        for c in p.coeffs.iter() {
+            coeffs.push(field.mul(&n, c));
+            n = field.add(&n, &field.one());
+        }
+        if !coeffs.is_empty() {
+            coeffs.remove(0);
+        }
+        self.polynomial(coeffs)
+    }
+
+    //monic greatest common divisor, zero only when both are zero
+    pub fn gcd(&self, a: &Polynomial<T>, b: &Polynomial<T>) -> Polynomial<T> {
+        let mut a = a.clone();
+        let mut b = b.clone();
+        while !b.is_zero() {
+            //b is not zero, so the division can't fail
+            let remainder = self.division_algorithm(&a, &b).unwrap().remainder;
+            a = b;
+            b = remainder;
+        }
+        self.monic(&a)
+    }
+
+    //base^exponent mod modulus by square and multiply
+    pub fn pow_mod(
+        &self,
+        base: &Polynomial<T>,
+        exponent: &BigUint,
+        modulus: &Polynomial<T>,
+    ) -> Result<Polynomial<T>, Error> {
+        let mut result = self.division_algorithm(&self.one(), modulus)?.remainder;
+        let base = self.division_algorithm(base, modulus)?.remainder;
+        let bytes = exponent.to_bytes_le();
+        for i in (0..exponent.bits()).rev() {
+            result = self
+                .division_algorithm(&self.mul(&result, &result), modulus)?
+                .remainder;
+            if (bytes[i / 8] >> (i % 8)) & 1 == 1 {
+                result = self
+                    .division_algorithm(&self.mul(&result, &base), modulus)?
+                    .remainder;
+            }
+        }
+        Ok(result)
+    }
+}
+
+//below this many coefficients in the shorter operand schoolbook multiplication is faster
+const KARATSUBA_THRESHOLD: usize = 32;
+
+//below this many coefficients in the shorter operand Karatsuba beats the transform
+//(benches/polynomial.rs puts the crossover between degree 63 and 127)
+const NTT_THRESHOLD: usize = 128;
+
+//lhs * rhs without normalising, the result has lhs.len() + rhs.len() - 1 coefficients
+fn schoolbook<T: Field>(
+    field: &T,
+    lhs: &[T::RingMember],
+    rhs: &[T::RingMember],
+) -> Vec<T::RingMember> {
+    if lhs.is_empty() || rhs.is_empty() {
+        return vec![];
+    }
+    let mut product = vec![field.zero(); lhs.len() + rhs.len() - 1];
+    for (i, l) in lhs.iter().enumerate() {
+        if *l == field.zero() {
+            continue;
+        }
+        for (p, r) in product[i..].iter_mut().zip(rhs.iter()) {
+            *p = field.add(p, &field.mul(l, r));
+        }
+    }
+    product
+}
+
+fn add_into<T: Field>(field: &T, target: &mut [T::RingMember], value: &[T::RingMember]) {
+    for (t, v) in target.iter_mut().zip(value.iter()) {
+        *t = field.add(t, v);
+    }
+}
+
+//Splits both operands at half the longer length, so (a0 + a1 x^h)(b0 + b1 x^h) needs the
+//three products a0 b0, a1 b1 and (a0 + a1)(b0 + b1)
+fn karatsuba<T: Field>(
+    field: &T,
+    lhs: &[T::RingMember],
+    rhs: &[T::RingMember],
+) -> Vec<T::RingMember> {
+    if lhs.len().min(rhs.len()) < KARATSUBA_THRESHOLD {
+        return schoolbook(field, lhs, rhs);
+    }
+    let half = (lhs.len().max(rhs.len()) + 1) / 2;
+    let (a0, a1) = lhs.split_at(half.min(lhs.len()));
+    let (b0, b1) = rhs.split_at(half.min(rhs.len()));
+    let low = karatsuba(field, a0, b0);
+    let high = karatsuba(field, a1, b1);
+    let mut a_sum = a0.to_vec();
+    add_into(field, &mut a_sum, a1);
+    let mut b_sum = b0.to_vec();
+    add_into(field, &mut b_sum, b1);
+    let mut middle = karatsuba(field, &a_sum, &b_sum);
+    for (m, l) in middle.iter_mut().zip(low.iter()) {
+        *m = field.add(m, &field.neg(l));
+    }
+    for (m, h) in middle.iter_mut().zip(high.iter()) {
+        *m = field.add(m, &field.neg(h));
+    }
+
+    let mut product = vec![field.zero(); lhs.len() + rhs.len() - 1];
+    add_into(field, &mut product, &low);
+    add_into(field, &mut product[half..], &middle);
+    if !high.is_empty() {
+        add_into(field, &mut product[2 * half..], &high);
+    }
+    product
+}
+
+//In place iterative Cooley-Tukey transform, root has to be a primitive root of unity of
+//order values.len()
+fn ntt<T: Field>(field: &T, values: &mut [T::RingMember], root: &T::RingMember) {
+    let n = values.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+    let mut len = 2;
+    while len <= n {
+        //root^(n / len) has order len
+        let mut step = root.clone();
+        let mut k = n / len;
+        while k > 1 {
+            step = field.mul(&step, &step);
+            k /= 2;
+        }
+        for chunk in values.chunks_mut(len) {
+            let (low, high) = chunk.split_at_mut(len / 2);
+            let mut w = field.one();
+            for (u, v) in low.iter_mut().zip(high.iter_mut()) {
+                let t = field.mul(&w, v);
+                *v = field.add(u, &field.neg(&t));
+                *u = field.add(u, &t);
+                w = field.mul(&w, &step);
+            }
+        }
+        len *= 2;
+    }
+}
+
+impl<'a, T: Field> PolynomialRing<'a, T> {
+    pub fn mul_schoolbook(&self, lhs: &Polynomial<T>, rhs: &Polynomial<T>) -> Polynomial<T> {
+        self.polynomial(schoolbook(self.underlying_field, &lhs.coeffs, &rhs.coeffs))
+    }
+
+    pub fn mul_karatsuba(&self, lhs: &Polynomial<T>, rhs: &Polynomial<T>) -> Polynomial<T> {
+        self.polynomial(karatsuba(self.underlying_field, &lhs.coeffs, &rhs.coeffs))
+    }
+
+    //Multiplication through the number theoretic transform, None if the field has no root
+    //of unity of the power of two order needed for the product
+    pub fn mul_ntt(&self, lhs: &Polynomial<T>, rhs: &Polynomial<T>) -> Option<Polynomial<T>> {
+        if lhs.is_zero() || rhs.is_zero() {
+            return Some(self.zero());
+        }
+        let field = self.underlying_field;
+        let size = (lhs.coeffs.len() + rhs.coeffs.len() - 1).next_power_of_two();
+        let root = field.root_of_unity(size)?;
+        let mut a = lhs.coeffs.clone();
+        a.resize(size, field.zero());
+        let mut b = rhs.coeffs.clone();
+        b.resize(size, field.zero());
+        ntt(field, &mut a, &root);
+        ntt(field, &mut b, &root);
+        for (x, y) in a.iter_mut().zip(b.iter()) {
+            *x = field.mul(x, y);
+        }
+        //the inverse transform is the transform with the inverse root, divided by size
+        ntt(field, &mut a, &field.inv(&root).ok()?);
+        let mut size_element = field.one();
+        let two = field.add(&field.one(), &field.one());
+        let mut k = size;
+        while k > 1 {
+            size_element = field.mul(&size_element, &two);
+            k /= 2;
+        }
+        let size_inv = field.inv(&size_element).ok()?;
+        for x in a.iter_mut() {
+            *x = field.mul(x, &size_inv);
+        }
+        Some(self.polynomial(a))
+    }
+}
+
+impl<'a, T: Field> Ring for PolynomialRing<'a, T> {
+    type RingMember = Polynomial<T>;
+
+    fn add(&self, lhs: &Self::RingMember, rhs: &Self::RingMember) -> Self::RingMember {
+        let field = self.underlying_field;
+        let (long, short) = if lhs.coeffs.len() >= rhs.coeffs.len() {
+            (lhs, rhs)
+        } else {
+            (rhs, lhs)
+        };
+        let mut sum_coeffs = long.coeffs.clone();
+        for (l, r) in sum_coeffs.iter_mut().zip(short.coeffs.iter()) {
+            *l = field.add(l, r);
+        }
+        self.polynomial(sum_coeffs)
+    }
+
+    //Schoolbook for short operands, Karatsuba for medium ones, and for long ones the number
+    //theoretic transform when the field has a large enough root of unity
+    fn mul(&self, lhs: &Self::RingMember, rhs: &Self::RingMember) -> Self::RingMember {
+        let shorter = lhs.coeffs.len().min(rhs.coeffs.len());
+        if shorter < KARATSUBA_THRESHOLD {
+            return self.mul_schoolbook(lhs, rhs);
+        }
+        if shorter < NTT_THRESHOLD {
+            return self.mul_karatsuba(lhs, rhs);
+        }
+        match self.mul_ntt(lhs, rhs) {
+            Some(product) => product,
+            None => self.mul_karatsuba(lhs, rhs),
+        }
+    }
+
+    fn neg(&self, lhs: &Self::RingMember) -> Self::RingMember {
+        let field = self.underlying_field;
+        self.polynomial(lhs.coeffs.iter().map(|c| field.neg(c)).collect())
+    }
+
+    fn zero(&self) -> Self::RingMember {
+        Polynomial { coeffs: vec![] }
+    }
+
+    fn one(&self) -> Self::RingMember {
+        self.polynomial(vec![self.underlying_field.one()])
+    }
+}
+
+impl<'a, T: Field> EuclidianDomain for PolynomialRing<'a, T> {
+    //polynomial long division
+    fn division_algorithm(
+        &self,
+        value: &Self::RingMember,
+        divisor: &Self::RingMember,
+    ) -> Result<DivisionAlgorithmResult<Self::RingMember>, Error> {
+        let field = self.underlying_field;
+        let divisor_degree = match divisor.degree() {
+            Some(degree) => degree,
+            None => return Err(Error::DivisionByZero),
+        };
+        let value_degree = match value.degree() {
+            Some(degree) if degree >= divisor_degree => degree,
+            _ => {
+                return Ok(DivisionAlgorithmResult {
+                    quotient: self.zero(),
+                    remainder: value.clone(),
+                })
+            }
+        };
+        let lc_inv = field.inv(&divisor.coeffs[divisor_degree])?;
+        let mut remainder = value.coeffs.clone();
+        let mut quotient = vec![field.zero(); value_degree - divisor_degree + 1];
+        for i in (0..quotient.len()).rev() {
+            let c = field.mul(&remainder[i + divisor_degree], &lc_inv);
+            if c == field.zero() {
+                continue;
+            }
+            let minus_c = field.neg(&c);
+            for (r, d) in remainder[i..].iter_mut().zip(divisor.coeffs.iter()) {
+                *r = field.add(r, &field.mul(&minus_c, d));
+            }
+            quotient[i] = c;
+        }
+        remainder.truncate(divisor_degree);
+        Ok(DivisionAlgorithmResult {
+            quotient: self.polynomial(quotient),
+            remainder: self.polynomial(remainder),
+        })
+    }
+
+    //makes polynomials monic
+    fn normalization_unit(&self, value: &Self::RingMember) -> Self::RingMember {
+        match value.leading_coefficient() {
+            //the leading coefficient of a normalised polynomial is never zero
+            Some(lc) => self.polynomial(vec![self.underlying_field.inv(lc).unwrap()]),
+            None => self.one(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::ModularField;
+    use crate::Matrix;
+    use num::BigUint;
+    use proptest::prelude::*;
+
+    fn poly<'a>(
+        ring: &PolynomialRing<'a, ModularField>,
+        coeffs: &[u64],
+    ) -> Polynomial<ModularField> {
+        ring.polynomial(coeffs.iter().map(|&c| BigUint::from(c)).collect())
+    }
+
+    #[test]
+    fn test_ring_operations() {
+        let field = ModularField::new(BigUint::from(7u64));
+        let ring = PolynomialRing::new(&field);
+        let a = poly(&ring, &[1, 2, 3]);
+        let b = poly(&ring, &[6, 5]);
+        assert_eq!(ring.add(&a, &b), poly(&ring, &[0, 0, 3]));
+        assert_eq!(ring.add(&b, &a), poly(&ring, &[0, 0, 3]));
+        assert_eq!(ring.add(&a, &ring.neg(&a)), ring.zero());
+        //(1 + 2x + 3x^2)(6 + 5x) = 6 + 17x + 28x^2 + 15x^3
+        assert_eq!(ring.mul(&a, &b), poly(&ring, &[6, 3, 0, 1]));
+        assert_eq!(ring.mul(&a, &ring.one()), a);
+        assert_eq!(ring.mul(&a, &ring.zero()), ring.zero());
+        assert_eq!(poly(&ring, &[1, 0, 0, 0]), poly(&ring, &[1]));
+        assert_eq!(a.degree(), Some(2));
+        assert_eq!(ring.zero().degree(), None);
+        assert_eq!(a.leading_coefficient(), Some(&BigUint::from(3u64)));
+        assert_eq!(ring.evaluate(&a, &BigUint::from(2u64)), BigUint::from(3u64));
+        assert_eq!(ring.derivative(&a), poly(&ring, &[2, 6]));
+        assert_eq!(
+            ring.monomial(BigUint::from(4u64), 2),
+            poly(&ring, &[0, 0, 4])
+        );
+    }
+
+    #[test]
+    fn test_extended_euclid() {
+        let field = ModularField::new(BigUint::from(7u64));
+        let ring = PolynomialRing::new(&field);
+        //(x - 1)(x - 2) and (x - 1)(x - 3)
+        let a = poly(&ring, &[2, 4, 1]);
+        let b = poly(&ring, &[3, 3, 1]);
+        let euclid = ring.extended_euclid(&a, &b).unwrap();
+        assert_eq!(ring.monic(&euclid.gcd), poly(&ring, &[6, 1]));
+        assert_eq!(
+            ring.add(&ring.mul(&euclid.x, &a), &ring.mul(&euclid.y, &b)),
+            euclid.gcd
+        );
+        assert!(ring.division_algorithm(&a, &ring.zero()).is_err());
+    }
+
+    #[test]
+    fn test_polynomial_matrix() {
+        let field = ModularField::new(BigUint::from(5u64));
+        let ring = PolynomialRing::new(&field);
+        let mat = Matrix::new(
+            &ring,
+            vec![
+                vec![poly(&ring, &[0, 1]), poly(&ring, &[1])],
+                vec![poly(&ring, &[1]), poly(&ring, &[0, 1])],
+            ],
+        );
+        //x^2 - 1
+        assert_eq!(
+            mat.division_free_determinant().unwrap(),
+            poly(&ring, &[4, 0, 1])
+        );
+        let (h, u) = mat.hermite_normal_form().unwrap();
+        assert_eq!(u.checked_mul(&mat).unwrap(), h);
+        assert_eq!(h[(1, 1)], poly(&ring, &[4, 0, 1]));
+    }
+
+    #[test]
+    fn test_multiplication_algorithms() {
+        //998244353 = 119 * 2^23 + 1 has roots of unity of every order up to 2^23
+        let field = ModularField::new(BigUint::from(998244353u64));
+        let ring = PolynomialRing::new(&field);
+        let a = ring.polynomial((0..300u64).map(|i| BigUint::from(i * i + 7)).collect());
+        let b = ring.polynomial((0..170u64).map(|i| BigUint::from(3 * i + 1)).collect());
+        let expected = ring.mul_schoolbook(&a, &b);
+        assert_eq!(ring.mul_karatsuba(&a, &b), expected);
+        assert_eq!(ring.mul_ntt(&a, &b), Some(expected.clone()));
+        assert_eq!(ring.mul(&a, &b), expected);
+        assert_eq!(expected.degree(), Some(468));
+
+        //7 - 1 = 6 has no factor 512, so only Karatsuba applies
+        let small = ModularField::new(BigUint::from(7u64));
+        let ring = PolynomialRing::new(&small);
+        let a = ring.polynomial((0..200u64).map(|i| BigUint::from(i % 7)).collect());
+        let b = ring.polynomial((0..100u64).map(|i| BigUint::from((i * 3) % 7)).collect());
+        assert!(ring.mul_ntt(&a, &b).is_none());
+        assert_eq!(ring.mul(&a, &b), ring.mul_schoolbook(&a, &b));
+    }
+
+    #[test]
+    fn test_composite_modulus_avoids_ntt() {
+        //129 = 3 * 43 and 64 divides 128, but Z/129 has no principal root of order 64
+        let field = ModularField::new(BigUint::from(129u64));
+        assert!(field.root_of_unity(64).is_none());
+        //2561 = 13 * 197 and 512 divides 2560, operands long enough for the transform
+        let field = ModularField::new(BigUint::from(2561u64));
+        assert!(field.root_of_unity(512).is_none());
+        let ring = PolynomialRing::new(&field);
+        let a = ring.polynomial((0..150u64).map(|i| BigUint::from(i * i % 2561)).collect());
+        let b = ring.polynomial((0..140u64).map(|i| BigUint::from(7 * i + 2)).collect());
+        assert!(ring.mul_ntt(&a, &b).is_none());
+        assert_eq!(ring.mul(&a, &b), ring.mul_schoolbook(&a, &b));
+    }
+
+    #[test]
+    fn test_root_of_unity() {
+        let field = ModularField::new(BigUint::from(17u64));
+        let root = field.root_of_unity(16).unwrap();
+        assert_eq!(
+            root.modpow(&BigUint::from(8u64), &BigUint::from(17u64)),
+            BigUint::from(16u64)
+        );
+        assert_eq!(field.root_of_unity(1), Some(field.one()));
+        assert!(field.root_of_unity(32).is_none());
+        assert!(field.root_of_unity(6).is_none());
+    }
+
+    proptest! {
+        #[test]
+        fn test_karatsuba(a in proptest::collection::vec(0u64..7, 0..120),
+                          b in proptest::collection::vec(0u64..7, 0..120)) {
+            let field = ModularField::new(BigUint::from(7u64));
+            let ring = PolynomialRing::new(&field);
+            let a = poly(&ring, &a);
+            let b = poly(&ring, &b);
+            assert_eq!(ring.mul_karatsuba(&a, &b), ring.mul_schoolbook(&a, &b));
+        }
+
+        #[test]
+        fn test_division(a in proptest::collection::vec(0u64..7, 0..8),
+                         b in proptest::collection::vec(0u64..7, 1..5)) {
+            let field = ModularField::new(BigUint::from(7u64));
+            let ring = PolynomialRing::new(&field);
+            let a = poly(&ring, &a);
+            let b = poly(&ring, &b);
+            if !b.is_zero() {
+                let result = ring.division_algorithm(&a, &b).unwrap();
+                assert!(result.remainder.degree() < b.degree());
+                assert_eq!(ring.add(&ring.mul(&result.quotient, &b), &result.remainder), a);
+            }
+        }
+    }
+}
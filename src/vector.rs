@@ -0,0 +1,321 @@
+/*
+ * Copyright  2020 Debasish Ray Chawdhuri
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do
+ * so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+ * EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+ * MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT
+ * HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+ * WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+ * DEALINGS IN THE SOFTWARE.
+ */
+use crate::error::Error;
+use crate::{Field, Matrix, Ring, RingRef};
+use std::ops::{Index, IndexMut};
+use std::sync::Arc;
+
+#[derive(PartialEq, Debug)]
+pub struct Vector<'a, F: Ring> {
+    ring: RingRef<'a, F>,
+    data: Vec<F::RingMember>,
+}
+
+impl<'a, F: Ring> Clone for Vector<'a, F> {
+    fn clone(&self) -> Self {
+        Vector {
+            ring: self.ring.clone(),
+            data: self.data.clone(),
+        }
+    }
+}
+
+impl<'a, F: Ring> Vector<'a, F> {
+    pub fn new(ring: &'a F, data: Vec<F::RingMember>) -> Self {
+        Vector {
+            ring: RingRef::Borrowed(ring),
+            data,
+        }
+    }
+
+    pub fn new_shared(ring: Arc<F>, data: Vec<F::RingMember>) -> Self {
+        Vector {
+            ring: RingRef::Shared(ring),
+            data,
+        }
+    }
+
+    pub fn zero(ring: &'a F, len: usize) -> Self {
+        Self::new(ring, vec![ring.zero(); len])
+    }
+
+    pub fn ring(&self) -> &F {
+        &self.ring
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn as_slice(&self) -> &[F::RingMember] {
+        &self.data
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, F::RingMember> {
+        self.data.iter()
+    }
+
+    pub fn into_vec(self) -> Vec<F::RingMember> {
+        self.data
+    }
+
+    fn check_len(&self, rhs: &Vector<F>) -> Result<(), Error> {
+        if self.len() != rhs.len() {
+            return Err(Error::DimensionMismatchForVectors(self.len(), rhs.len()));
+        }
+        Ok(())
+    }
+
+    pub fn dot(&self, rhs: &Vector<F>) -> Result<F::RingMember, Error> {
+        self.check_len(rhs)?;
+        let ring = &*self.ring;
+        Ok(self
+            .data
+            .iter()
+            .zip(rhs.data.iter())
+            .fold(ring.zero(), |sum, (x, y)| ring.add(&sum, &ring.mul(x, y))))
+    }
+
+    //self = self + a * x
+    pub fn axpy(&mut self, a: &F::RingMember, x: &Vector<F>) -> Result<(), Error> {
+        self.check_len(x)?;
+        let ring = &self.ring;
+        for (y, x) in self.data.iter_mut().zip(x.data.iter()) {
+            *y = ring.add(y, &ring.mul(a, x));
+        }
+        Ok(())
+    }
+
+    pub fn scale(&self, scalar: &F::RingMember) -> Vector<'a, F> {
+        Vector {
+            ring: self.ring.clone(),
+            data: self.data.iter().map(|x| self.ring.mul(scalar, x)).collect(),
+        }
+    }
+
+    pub fn add(&self, rhs: &Vector<F>) -> Result<Vector<'a, F>, Error> {
+        let mut result = self.clone();
+        result.axpy(&self.ring.one(), rhs)?;
+        Ok(result)
+    }
+
+    pub fn sub(&self, rhs: &Vector<F>) -> Result<Vector<'a, F>, Error> {
+        let mut result = self.clone();
+        result.axpy(&self.ring.neg(&self.ring.one()), rhs)?;
+        Ok(result)
+    }
+
+    //<self, self>, which is the squared euclidian norm over the reals
+    pub fn norm_squared(&self) -> F::RingMember {
+        let ring = &*self.ring;
+        self.data
+            .iter()
+            .fold(ring.zero(), |sum, x| ring.add(&sum, &ring.mul(x, x)))
+    }
+
+    //self^T * matrix, treating self as a row vector
+    pub fn mul_matrix(&self, matrix: &Matrix<F>) -> Result<Vector<'a, F>, Error> {
+        if self.len() != matrix.rows {
+            return Err(Error::DimensionMismatchForMatrixMultiplication(
+                1,
+                self.len(),
+                matrix.rows,
+                matrix.columns,
+            ));
+        }
+        let ring = &*self.ring;
+        let mut data = vec![ring.zero(); matrix.columns];
+        for (x, row) in self.data.iter().zip(matrix.row_iter()) {
+            if *x == ring.zero() {
+                continue;
+            }
+            for (ans, value) in data.iter_mut().zip(row.iter()) {
+                *ans = ring.add(ans, &ring.mul(x, value));
+            }
+        }
+        Ok(Vector {
+            ring: self.ring.clone(),
+            data,
+        })
+    }
+
+    pub fn to_column_matrix(&self) -> Matrix<'a, F> {
+        Matrix {
+            ring: self.ring.clone(),
+            rows: self.len(),
+            columns: 1,
+            data: self.data.clone(),
+        }
+    }
+
+    pub fn to_row_matrix(&self) -> Matrix<'a, F> {
+        Matrix {
+            ring: self.ring.clone(),
+            rows: 1,
+            columns: self.len(),
+            data: self.data.clone(),
+        }
+    }
+
+    //Accepts a single row or a single column
+    pub fn from_matrix(matrix: &Matrix<'a, F>) -> Result<Vector<'a, F>, Error> {
+        if matrix.rows != 1 && matrix.columns != 1 {
+            return Err(Error::ConversionOfNonVectorMatrix(
+                matrix.rows,
+                matrix.columns,
+            ));
+        }
+        Ok(Vector {
+            ring: matrix.ring.clone(),
+            data: matrix.data.clone(),
+        })
+    }
+}
+
+impl<'a, F: Field> Vector<'a, F> {
+    //largest magnitude of an entry
+    pub fn max_norm(&self) -> f64 {
+        self.data
+            .iter()
+            .map(|x| self.ring.magnitude(x))
+            .fold(0.0, f64::max)
+    }
+}
+
+impl<'a, F: Ring> Index<usize> for Vector<'a, F> {
+    type Output = F::RingMember;
+
+    fn index(&self, i: usize) -> &F::RingMember {
+        &self.data[i]
+    }
+}
+
+impl<'a, F: Ring> IndexMut<usize> for Vector<'a, F> {
+    fn index_mut(&mut self, i: usize) -> &mut F::RingMember {
+        &mut self.data[i]
+    }
+}
+
+impl<'a, F: Ring> From<Vector<'a, F>> for Matrix<'a, F> {
+    //vectors are columns by default
+    fn from(vector: Vector<'a, F>) -> Self {
+        Matrix {
+            ring: vector.ring,
+            rows: vector.data.len(),
+            columns: 1,
+            data: vector.data,
+        }
+    }
+}
+
+impl<'a, F: Ring> Matrix<'a, F> {
+    //self * vector, treating vector as a column
+    pub fn mul_vector(&self, vector: &Vector<F>) -> Result<Vector<'a, F>, Error> {
+        if self.columns != vector.len() {
+            return Err(Error::DimensionMismatchForMatrixMultiplication(
+                self.rows,
+                self.columns,
+                vector.len(),
+                1,
+            ));
+        }
+        let ring = &*self.ring;
+        let data = self
+            .row_iter()
+            .map(|row| {
+                row.iter()
+                    .zip(vector.data.iter())
+                    .fold(ring.zero(), |sum, (x, y)| ring.add(&sum, &ring.mul(x, y)))
+            })
+            .collect();
+        Ok(Vector {
+            ring: self.ring.clone(),
+            data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::{F64Field, ModularField};
+    use crate::int_ring::I32Ring;
+    use crate::matrix;
+    use num::BigUint;
+
+    #[test]
+    fn test_dot_and_axpy() {
+        let ring = I32Ring;
+        let mut x = Vector::new(&ring, vec![1, 2, 3]);
+        let y = Vector::new(&ring, vec![4, -5, 6]);
+        assert_eq!(x.dot(&y).unwrap(), 12);
+        assert_eq!(x.norm_squared(), 14);
+        x.axpy(&2, &y).unwrap();
+        assert_eq!(x.as_slice(), &[9, -8, 15]);
+        assert_eq!(x.sub(&y).unwrap(), Vector::new(&ring, vec![5, -3, 9]));
+        assert_eq!(y.scale(&-1)[1], 5);
+        assert!(matches!(
+            x.dot(&Vector::new(&ring, vec![1])),
+            Err(Error::DimensionMismatchForVectors(3, 1))
+        ));
+    }
+
+    #[test]
+    fn test_matrix_vector_products() {
+        let ring = ModularField::new(BigUint::from(7u64));
+        let n = |x: u64| BigUint::from(x);
+        let mat: Matrix<ModularField> =
+            Matrix::new(&ring, vec![vec![n(1), n(2), n(3)], vec![n(4), n(5), n(6)]]);
+        let x = Vector::new(&ring, vec![n(1), n(0), n(2)]);
+        let y = Vector::new(&ring, vec![n(3), n(1)]);
+        let ax = mat.mul_vector(&x).unwrap();
+        assert_eq!(ax.as_slice(), &[n(0), n(2)]);
+        assert_eq!(
+            Matrix::from(ax),
+            mat.checked_mul(&x.to_column_matrix()).unwrap()
+        );
+        let ya = y.mul_matrix(&mat).unwrap();
+        assert_eq!(
+            ya.to_row_matrix(),
+            y.to_row_matrix().checked_mul(&mat).unwrap()
+        );
+        assert!(mat.mul_vector(&y).is_err());
+        assert!(x.mul_matrix(&mat).is_err());
+    }
+
+    #[test]
+    fn test_matrix_conversions() {
+        let ring = F64Field;
+        let column = matrix!(&ring, [1.0; -4.0; 2.0]);
+        let v = Vector::from_matrix(&column).unwrap();
+        assert_eq!(v.to_column_matrix(), column);
+        assert_eq!(v.to_row_matrix(), column.transpose());
+        assert_eq!(Vector::from_matrix(&column.transpose()).unwrap(), v);
+        assert_eq!(v.max_norm(), 4.0);
+        assert!(Vector::from_matrix(&Matrix::one(&ring, 2)).is_err());
+    }
+}